@@ -0,0 +1,354 @@
+//! Backups of files and directories affected by changes. Every operation gets
+//! its own backup set, a directory named after the id of the set. Every backup
+//! is a copy of the raw bytes of the original, stored as a uniquely named blob
+//! in the backup set and recorded in the manifest of the set, which maps the
+//! original path to its blob.
+//!
+//! Backup sets live outside of the project by default, in a per-user data
+//! directory keyed by the id of the project, so that renaming the project
+//! root does not move the backups of the operation that renames it.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use walkdir::WalkDir;
+
+use crate::changes::rename_with;
+
+/// The name of the manifest file in a backup directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The manifest of a backup set.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Manifest {
+    /// The id of the backup set, which is also the name of its directory.
+    pub id: String,
+    /// A description of the operation that made the backups.
+    pub operation: String,
+    /// When the backup set was created.
+    pub created_at: DateTime<Utc>,
+    /// The backups and renames in the set, in the order they were made.
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn new(id: impl Into<String>, operation: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            operation: operation.into(),
+            created_at: Utc::now(),
+            entries: vec![],
+        }
+    }
+}
+
+/// A backup of a single file or directory, or a rename of one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ManifestEntry {
+    /// The path of the original file or directory at the time of the backup
+    /// or rename.
+    pub original: PathBuf,
+    /// The name of the blob in the backup directory, empty for renames.
+    pub blob: String,
+    /// Whether the backup is of a file or a directory, or a rename.
+    pub kind: BackupKind,
+    /// Where the original was renamed to, for renames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<PathBuf>,
+}
+
+/// The kind of a backup.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupKind {
+    File,
+    Dir,
+    Rename,
+}
+
+/// The file in a project that holds the id of the project, followed by the
+/// path of the project that the id was assigned to.
+pub const PROJECT_ID_FILE: &str = ".renom/id";
+
+/// The directory to store new backup sets of a project in: the given backup
+/// directory if any, otherwise a directory in the per-user data directory
+/// keyed by the id of the project, which is assigned if needed. The returned
+/// path is absolute.
+pub fn backup_root(project_root: &Path, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let backup_root = match backup_dir {
+        Some(backup_dir) => backup_dir.to_owned(),
+        None => default_backup_root(&assign_project_id(project_root)?)?,
+    };
+    absolute(backup_root)
+}
+
+/// The directory that holds the existing backup sets of a project, like
+/// [`backup_root`] but without assigning an id. Returns `None` if the project
+/// has no id and so no backups in the per-user data directory.
+pub fn find_backup_root(
+    project_root: &Path,
+    backup_dir: Option<&Path>,
+) -> Result<Option<PathBuf>, String> {
+    let backup_root = match backup_dir {
+        Some(backup_dir) => backup_dir.to_owned(),
+        None => match project_id(project_root)? {
+            Some(id) => default_backup_root(&id)?,
+            None => return Ok(None),
+        },
+    };
+    absolute(backup_root).map(Some)
+}
+
+/// Read the id of a project, if it has one. The id is stored in the project
+/// so that it survives renames. A copy of a project that still exists at the
+/// path recorded with the id does not share its id.
+pub fn project_id(project_root: &Path) -> Result<Option<String>, String> {
+    let Some((id, recorded_root)) = read_project_id_file(project_root) else {
+        return Ok(None);
+    };
+    let project_root = fs::canonicalize(project_root).map_err(|err| err.to_string())?;
+    let is_copy = recorded_root.is_some_and(|recorded_root| {
+        recorded_root != project_root
+            && read_project_id_file(&recorded_root).is_some_and(|(other_id, _)| other_id == id)
+    });
+    Ok((!is_copy).then_some(id))
+}
+
+/// Read the id of a project, assigning a new one if the project has none, and
+/// record the current path of the project along with it.
+pub fn assign_project_id(project_root: &Path) -> Result<String, String> {
+    let id = project_id(project_root)?.unwrap_or_else(|| Ulid::new().to_string());
+    let canonical_root = fs::canonicalize(project_root).map_err(|err| err.to_string())?;
+    let content = format!("{}\n{}\n", id, canonical_root.display());
+    let id_file = project_root.join(PROJECT_ID_FILE);
+    if fs::read_to_string(&id_file).ok().as_deref() != Some(content.as_str()) {
+        fs::create_dir_all(id_file.parent().unwrap()).map_err(|err| err.to_string())?;
+        fs::write(&id_file, content).map_err(|err| err.to_string())?;
+    }
+    Ok(id)
+}
+
+/// Read the id of a project and the path recorded with it, if any.
+fn read_project_id_file(project_root: &Path) -> Option<(String, Option<PathBuf>)> {
+    let content = fs::read_to_string(project_root.join(PROJECT_ID_FILE)).ok()?;
+    let mut lines = content.lines().map(str::trim);
+    let id = lines.next().filter(|id| !id.is_empty())?.to_owned();
+    let recorded_root = lines
+        .next()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    Some((id, recorded_root))
+}
+
+fn default_backup_root(project_id: &str) -> Result<PathBuf, String> {
+    Ok(user_data_dir()?
+        .join("renom")
+        .join("backups")
+        .join(project_id))
+}
+
+fn absolute(path: PathBuf) -> Result<PathBuf, String> {
+    match path.is_absolute() {
+        true => Ok(path),
+        false => Ok(env::current_dir()
+            .map_err(|err| err.to_string())?
+            .join(path)),
+    }
+}
+
+/// The per-user data directory of the platform: `%LOCALAPPDATA%` on Windows,
+/// `~/Library/Application Support` on macOS and `$XDG_DATA_HOME` or
+/// `~/.local/share` elsewhere.
+fn user_data_dir() -> Result<PathBuf, String> {
+    let var = |name| {
+        env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let data_dir = if cfg!(windows) {
+        var("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local/share")))
+    };
+    data_dir.ok_or_else(|| "could not locate the user data directory, pass a backup dir".into())
+}
+
+/// Create a new, empty backup set for an operation in the backup root.
+/// Returns the directory of the backup set.
+pub fn create_backup_set(backup_root: &Path, operation: &str) -> Result<PathBuf, String> {
+    let id = Ulid::new().to_string();
+    let backup_dir = backup_root.join(&id);
+    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
+    write_manifest(&backup_dir, &Manifest::new(id, operation)).map_err(|err| err.to_string())?;
+    Ok(backup_dir)
+}
+
+/// Find the directory of an existing backup set in the backup root. Returns an
+/// error if the id is not a valid backup set id or if the set does not exist.
+pub fn find_backup_set(backup_root: &Path, id: &str) -> Result<PathBuf, String> {
+    Ulid::from_string(id).map_err(|_| format!("{} is not a valid backup set id", id))?;
+    let backup_dir = backup_root.join(id);
+    match backup_dir.is_dir() {
+        true => Ok(backup_dir),
+        false => Err(format!("backup set {} does not exist", id)),
+    }
+}
+
+/// Find all backup sets in the backup root, ordered from oldest to newest.
+/// Directories without a manifest are skipped.
+pub fn find_backup_sets(backup_root: &Path) -> Result<Vec<Manifest>, String> {
+    if !backup_root.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut manifests = fs::read_dir(backup_root)
+        .map_err(|err| err.to_string())?
+        .filter_map(Result::ok)
+        .filter_map(|entry| read_manifest(&entry.path()).ok())
+        .collect::<Vec<_>>();
+    manifests.sort_by_key(|manifest| manifest.created_at);
+    Ok(manifests)
+}
+
+/// Restore every file and directory in a backup set to its original path and
+/// revert the recorded renames. Entries are restored from newest to oldest,
+/// so that a path that was backed up more than once ends up in its earliest
+/// state, and renamed paths are moved back before their content is restored.
+pub fn restore_backup_set(backup_dir: &Path) -> Result<Manifest, String> {
+    let manifest = read_manifest(backup_dir).map_err(|err| err.to_string())?;
+    for entry in manifest.entries.iter().rev() {
+        let restored = match &entry.renamed_to {
+            Some(renamed_to) => {
+                rename_with(renamed_to, &entry.original, |from, to| fs::rename(from, to))
+            }
+            None => restore_path(&backup_dir.join(&entry.blob), &entry.original),
+        };
+        restored.map_err(|err| format!("{}: {}", entry.original.display(), err))?;
+    }
+    Ok(manifest)
+}
+
+/// Compute the total size in bytes of the blobs in a backup set.
+pub fn backup_set_size(backup_dir: &Path) -> u64 {
+    WalkDir::new(backup_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Read the manifest of a backup set.
+pub fn read_manifest(backup_dir: &Path) -> io::Result<Manifest> {
+    let content = fs::read_to_string(backup_dir.join(MANIFEST_FILE))?;
+    serde_json::from_str(&content).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_manifest(backup_dir: &Path, manifest: &Manifest) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(backup_dir.join(MANIFEST_FILE), content)
+}
+
+/// Back up a file or directory into a backup set and record it in the
+/// manifest of the set. A manifest is created for directories that are not
+/// backup sets yet. Returns the path of the blob.
+pub fn backup_path(path: &Path, backup_dir: &Path) -> io::Result<PathBuf> {
+    let blob = Ulid::new().to_string();
+    let blob_path = backup_dir.join(&blob);
+    let kind = match path.is_dir() {
+        true => {
+            copy_dir(path, &blob_path)?;
+            BackupKind::Dir
+        }
+        false => {
+            fs::copy(path, &blob_path)?;
+            BackupKind::File
+        }
+    };
+    record_entry(backup_dir, entry(path, blob, kind))?;
+    Ok(blob_path)
+}
+
+/// Record a rename in the manifest of a backup set, so that restoring the set
+/// also reverts the rename.
+pub fn record_rename(from: &Path, to: &Path, backup_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(backup_dir)?;
+    record_entry(
+        backup_dir,
+        ManifestEntry {
+            renamed_to: Some(to.to_owned()),
+            ..entry(from, String::new(), BackupKind::Rename)
+        },
+    )
+}
+
+/// Forget the most recent rename recorded in the manifest of a backup set,
+/// once the rename has been reverted.
+pub fn forget_rename(backup_dir: &Path) -> io::Result<()> {
+    let mut manifest = read_manifest(backup_dir)?;
+    if let Some(index) = manifest
+        .entries
+        .iter()
+        .rposition(|entry| entry.kind == BackupKind::Rename)
+    {
+        manifest.entries.remove(index);
+    }
+    write_manifest(backup_dir, &manifest)
+}
+
+fn entry(path: &Path, blob: String, kind: BackupKind) -> ManifestEntry {
+    ManifestEntry {
+        original: path.to_owned(),
+        blob,
+        kind,
+        renamed_to: None,
+    }
+}
+
+fn record_entry(backup_dir: &Path, entry: ManifestEntry) -> io::Result<()> {
+    let mut manifest = match read_manifest(backup_dir) {
+        Ok(manifest) => manifest,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let id = backup_dir.file_name().unwrap_or_default().to_string_lossy();
+            Manifest::new(id, "unknown")
+        }
+        Err(err) => return Err(err),
+    };
+    manifest.entries.push(entry);
+    write_manifest(backup_dir, &manifest)
+}
+
+/// Restore a file or directory from its blob, replacing whatever is at the
+/// original path.
+pub fn restore_path(blob_path: &Path, original: &Path) -> io::Result<()> {
+    if blob_path.is_dir() {
+        if original.is_dir() {
+            fs::remove_dir_all(original)?;
+        }
+        return copy_dir(blob_path, original);
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(blob_path, original).map(|_| ())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from).unwrap());
+        match entry.file_type().is_dir() {
+            true => fs::create_dir_all(&target)?,
+            false => fs::copy(entry.path(), &target).map(|_| ())?,
+        }
+    }
+    Ok(())
+}
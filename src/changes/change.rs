@@ -1,21 +1,33 @@
 use std::{
     fmt::Display,
-    io,
+    fs, io,
     path::{Path, PathBuf},
 };
 
-use ini::{Ini, ParseOption};
-use regex::Regex;
-use sha2::{Digest, Sha256};
+use ini::{
+    ini::{LineSeparator, WriteOption},
+    Ini, ParseOption,
+};
+
+use crate::{
+    backup::{backup_path, restore_path},
+    encoding::{read_text, write_text, LineEnding, TextFile},
+    package::{name_references_package, replace_names},
+};
 
-use super::{rename_file::RenameFile, AppendIniEntry, ReplaceInFile, SetIniEntry};
+use super::{
+    rename_file::{rename_with, RenameFile},
+    AppendIniEntry, RemovePath, ReplaceInAsset, ReplaceInFile, SetIniEntry,
+};
 
 #[derive(Debug, PartialEq)]
 pub enum Change {
     RenameFile(RenameFile),
     ReplaceInFile(ReplaceInFile),
+    ReplaceInAsset(ReplaceInAsset),
     SetIniEntry(SetIniEntry),
     AppendIniEntry(AppendIniEntry),
+    RemovePath(RemovePath),
 }
 
 impl Change {
@@ -23,29 +35,96 @@ impl Change {
         match &*self {
             Change::RenameFile(params) => Change::rename_file(&params),
             Change::ReplaceInFile(params) => Change::replace_in_file(params, backup_dir),
+            Change::ReplaceInAsset(params) => Change::replace_in_asset(params, backup_dir),
             Change::SetIniEntry(params) => Change::set_ini_entry(params, backup_dir),
             Change::AppendIniEntry(params) => Change::append_ini_entry(params, backup_dir),
+            Change::RemovePath(params) => Change::remove_path(params),
+        }
+    }
+
+    /// Map every path in the change, e.g. to where it has moved to after
+    /// earlier renames.
+    pub fn map_paths(&mut self, map: impl Fn(&Path) -> PathBuf) {
+        match self {
+            Change::RenameFile(params) => {
+                params.from = map(&params.from);
+                params.to = map(&params.to);
+            }
+            Change::ReplaceInFile(params) => params.path = map(&params.path),
+            Change::ReplaceInAsset(params) => params.path = map(&params.path),
+            Change::SetIniEntry(params) => params.path = map(&params.path),
+            Change::AppendIniEntry(params) => params.path = map(&params.path),
+            Change::RemovePath(params) => params.path = map(&params.path),
+        }
+    }
+
+    /// The path that the change applies to, which is the original path for
+    /// renames.
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::RenameFile(params) => &params.from,
+            Change::ReplaceInFile(params) => &params.path,
+            Change::ReplaceInAsset(params) => &params.path,
+            Change::SetIniEntry(params) => &params.path,
+            Change::AppendIniEntry(params) => &params.path,
+            Change::RemovePath(params) => &params.path,
+        }
+    }
+
+    /// The file whose content the change edits, if any.
+    pub fn edited_path(&self) -> Option<&Path> {
+        match self {
+            Change::RenameFile(_) | Change::RemovePath(_) => None,
+            Change::ReplaceInFile(params) => Some(&params.path),
+            Change::ReplaceInAsset(params) => Some(&params.path),
+            Change::SetIniEntry(params) => Some(&params.path),
+            Change::AppendIniEntry(params) => Some(&params.path),
         }
     }
 
     fn rename_file(params: &RenameFile) -> io::Result<Revert> {
         let from = params.from.clone();
         let to = params.to.clone();
-        std::fs::rename(&from, &to)?;
+        rename_with(&from, &to, |from, to| std::fs::rename(from, to))?;
 
-        Ok(Box::new(move || std::fs::rename(&to, &from)))
+        Ok(Box::new(move |_: &Path| {
+            rename_with(&to, &from, |from, to| std::fs::rename(from, to))
+        }))
     }
 
     fn replace_in_file(params: &ReplaceInFile, backup_dir: &Path) -> io::Result<Revert> {
-        let backup = Change::backup_file(&params.path, backup_dir)?;
+        let blob = Change::backup(&params.path, backup_dir)?;
         let target = params.path.clone();
-        let content = std::fs::read_to_string(&target)?;
-        let regex = Regex::new(&params.from).expect("regex should be valid");
-        let content_after_replace = regex.replace_all(&content, params.to.as_str()).to_string();
-        std::fs::write(&target, &content_after_replace)?;
+        let file = read_text(&target)?;
+        let content_after_replace = params.replace(&file.content);
+        write_text(&target, &content_after_replace, file.encoding)?;
 
-        Ok(Box::new(move || {
-            std::fs::copy(&backup, &target).map(|_| ())
+        Ok(Box::new(move |backup_dir: &Path| {
+            restore_path(&backup_dir.join(&blob), &target)
+        }))
+    }
+
+    fn replace_in_asset(params: &ReplaceInAsset, backup_dir: &Path) -> io::Result<Revert> {
+        let blob = Change::backup(&params.path, backup_dir)?;
+        let target = params.path.clone();
+        let mut bytes = std::fs::read(&target)?;
+        let (_, skipped) = replace_names(&mut bytes, |name| {
+            name_references_package(name, &params.from)
+                .then(|| format!("{}{}", params.to, &name[params.from.len()..]))
+        })
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if !skipped.is_empty() {
+            let error_message = format!(
+                "names {} cannot be replaced in place in asset {}",
+                skipped.join(", "),
+                target.display()
+            );
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error_message));
+        }
+        std::fs::write(&target, &bytes)?;
+
+        Ok(Box::new(move |backup_dir: &Path| {
+            restore_path(&backup_dir.join(&blob), &target)
         }))
     }
 
@@ -57,25 +136,16 @@ impl Change {
             path,
         } = params;
 
-        let backup = Change::backup_file(path, backup_dir)?;
+        let blob = Change::backup(path, backup_dir)?;
         let target = path.clone();
 
-        let read_opts = ParseOption {
-            enabled_escape: false,
-            enabled_quote: false,
-        };
-        let mut ini = match Ini::load_from_file_opt(&target, read_opts) {
-            Ok(ini) => ini,
-            Err(err) => match err {
-                ini::ini::Error::Io(io) => return Err(io),
-                ini::ini::Error::Parse(p) => return Err(io::Error::new(io::ErrorKind::Other, p)),
-            },
-        };
+        let file = read_text(&target)?;
+        let mut ini = Change::load_ini(&file)?;
         ini.with_section(Some(section)).set(key, value);
-        ini.write_to_file(&target)?;
+        Change::write_ini(&ini, &target, &file)?;
 
-        Ok(Box::new(move || {
-            std::fs::copy(&backup, &target).map(|_| ())
+        Ok(Box::new(move |backup_dir: &Path| {
+            restore_path(&backup_dir.join(&blob), &target)
         }))
     }
 
@@ -87,36 +157,61 @@ impl Change {
             path,
         } = params;
 
-        let backup = Change::backup_file(path, backup_dir)?;
+        let blob = Change::backup(path, backup_dir)?;
         let target = path.clone();
 
-        let read_opts = ParseOption {
-            enabled_escape: false,
-            enabled_quote: false,
-        };
-        let mut ini = match Ini::load_from_file_opt(&target, read_opts) {
-            Ok(ini) => ini,
-            Err(err) => match err {
-                ini::ini::Error::Io(io) => return Err(io),
-                ini::ini::Error::Parse(p) => return Err(io::Error::new(io::ErrorKind::Other, p)),
-            },
-        };
+        let file = read_text(&target)?;
+        let mut ini = Change::load_ini(&file)?;
         ini.with_section(Some(section)).set("dummy", "dummy"); // create if does not exist
         ini.section_mut(Some(section)).unwrap().append(key, value);
         ini.with_section(Some(section)).delete(&"dummy");
-        ini.write_to_file(&params.path)?;
+        Change::write_ini(&ini, &target, &file)?;
 
-        Ok(Box::new(move || {
-            std::fs::copy(&backup, &target).map(|_| ())
+        Ok(Box::new(move |backup_dir: &Path| {
+            restore_path(&backup_dir.join(&blob), &target)
         }))
     }
 
-    fn backup_file(file: &Path, backup_dir: &Path) -> io::Result<PathBuf> {
-        let content = std::fs::read_to_string(file)?;
-        let hash = Sha256::digest(&content);
-        let path = backup_dir.join(format!("{:x}", hash));
-        std::fs::write(&path, &content)?;
-        Ok(path)
+    fn remove_path(params: &RemovePath) -> io::Result<Revert> {
+        match params.path.is_dir() {
+            true => fs::remove_dir_all(&params.path)?,
+            false => fs::remove_file(&params.path)?,
+        }
+        Ok(Box::new(|_: &Path| Ok(())))
+    }
+
+    /// Back up a file and return the path of its blob relative to the backup
+    /// directory, so that reverts can find the blob even if the backup
+    /// directory has moved since.
+    fn backup(path: &Path, backup_dir: &Path) -> io::Result<PathBuf> {
+        let blob = backup_path(path, backup_dir)?;
+        Ok(blob.strip_prefix(backup_dir).unwrap_or(&blob).to_owned())
+    }
+
+    fn load_ini(file: &TextFile) -> io::Result<Ini> {
+        let read_opts = ParseOption {
+            enabled_escape: false,
+            enabled_quote: false,
+        };
+        Ini::load_from_str_opt(&file.content, read_opts)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    /// Write an ini file in the encoding and with the line ending of the file
+    /// that it was loaded from.
+    fn write_ini(ini: &Ini, target: &Path, file: &TextFile) -> io::Result<()> {
+        let write_opts = WriteOption {
+            line_separator: match file.line_ending {
+                LineEnding::Lf => LineSeparator::CR,
+                LineEnding::CrLf => LineSeparator::CRLF,
+            },
+            ..WriteOption::default()
+        };
+        let mut content = vec![];
+        ini.write_to_opt(&mut content, write_opts)?;
+        let content = String::from_utf8(content)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_text(target, &content, file.encoding)
     }
 }
 
@@ -125,10 +220,14 @@ impl Display for Change {
         match &*self {
             Change::RenameFile(params) => write!(f, "{}", &params),
             Change::ReplaceInFile(params) => write!(f, "{}", &params),
+            Change::ReplaceInAsset(params) => write!(f, "{}", &params),
             Change::SetIniEntry(params) => write!(f, "{}", &params),
             Change::AppendIniEntry(params) => write!(f, "{}", &params),
+            Change::RemovePath(params) => write!(f, "{}", &params),
         }
     }
 }
 
-pub type Revert = Box<dyn Fn() -> io::Result<()>>;
+/// Reverts an applied change, given the current location of the backup
+/// directory that the change was applied with.
+pub type Revert = Box<dyn Fn(&Path) -> io::Result<()>>;
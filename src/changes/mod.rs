@@ -1,11 +1,17 @@
 mod append_ini_entry;
 mod change;
+mod remove_path;
 mod rename_file;
+mod replace_in_asset;
 mod replace_in_file;
 mod set_ini_entry;
+mod summary;
 
 pub use append_ini_entry::*;
 pub use change::*;
+pub use remove_path::*;
 pub use rename_file::*;
+pub use replace_in_asset::*;
 pub use replace_in_file::*;
 pub use set_ini_entry::*;
+pub use summary::*;
@@ -0,0 +1,31 @@
+use std::{fmt::Display, path::PathBuf};
+
+use colored::Colorize;
+
+/// Remove a generated file or directory, such as build products. The engine
+/// regenerates them, so the path is deleted rather than backed up and the
+/// removal cannot be reverted.
+#[derive(Debug, PartialEq)]
+pub struct RemovePath {
+    pub path: PathBuf,
+}
+
+impl RemovePath {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Display for RemovePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "remove {}",
+            &self
+                .path
+                .to_str()
+                .unwrap_or("invalid Unicode path")
+                .dimmed()
+        )
+    }
+}
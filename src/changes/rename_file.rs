@@ -1,4 +1,8 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
 
 use colored::Colorize;
 
@@ -15,6 +19,34 @@ impl RenameFile {
             to: to.into(),
         }
     }
+
+    /// Whether the rename only changes the case of the path, which
+    /// case-insensitive file systems consider to be the same path.
+    pub fn is_case_only(&self) -> bool {
+        self.from != self.to
+            && self
+                .from
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&self.to.to_string_lossy())
+    }
+}
+
+/// Rename a path with the given function. Case-only renames go through a
+/// temporary intermediate name so that they also work on case-insensitive
+/// file systems.
+pub fn rename_with(
+    from: &Path,
+    to: &Path,
+    rename: impl Fn(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    if !RenameFile::new(from, to).is_case_only() {
+        return rename(from, to);
+    }
+    let mut intermediate = to.as_os_str().to_owned();
+    intermediate.push(".renom-tmp");
+    let intermediate = PathBuf::from(intermediate);
+    rename(from, &intermediate)?;
+    rename(&intermediate, to)
 }
 
 impl Display for RenameFile {
@@ -0,0 +1,39 @@
+use std::{fmt::Display, path::PathBuf};
+
+use colored::Colorize;
+
+/// Replace references to a package in the name table of an asset. Names that
+/// reference the package or objects within it are rewritten in place, which
+/// requires the replacement to have the same length.
+#[derive(Debug, PartialEq)]
+pub struct ReplaceInAsset {
+    pub path: PathBuf,
+    pub from: String,
+    pub to: String,
+}
+
+impl ReplaceInAsset {
+    pub fn new(path: impl Into<PathBuf>, from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+impl Display for ReplaceInAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "replace package {} with {} in asset {}",
+            &self.from.dimmed(),
+            &self.to.dimmed(),
+            &self
+                .path
+                .to_str()
+                .unwrap_or("invalid Unicode path")
+                .dimmed()
+        )
+    }
+}
@@ -1,12 +1,18 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{borrow::Cow, fmt::Display, ops::Range, path::PathBuf};
 
 use colored::Colorize;
+use regex::Regex;
+
+/// Matches the old name of a redirect, e.g. `OldName="/Script/Old"`.
+const REDIRECT_OLD_NAME: &str = r#"\bOldName\s*=\s*"[^"]*""#;
 
 #[derive(Debug, PartialEq)]
 pub struct ReplaceInFile {
     pub path: PathBuf,
     pub from: String,
     pub to: String,
+    /// Matches regions of the file that are left alone, if any.
+    pub skip: Option<String>,
 }
 
 impl ReplaceInFile {
@@ -15,8 +21,74 @@ impl ReplaceInFile {
             path: path.into(),
             from: from.into(),
             to: to.into(),
+            skip: None,
+        }
+    }
+
+    /// Replace in a config file, leaving the old names of redirects alone.
+    pub fn in_config(
+        path: impl Into<PathBuf>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        Self {
+            skip: Some(REDIRECT_OLD_NAME.into()),
+            ..Self::new(path, from, to)
+        }
+    }
+
+    /// The ranges of the content that the replacement applies to.
+    pub fn find_ranges(&self, content: &str) -> Vec<Range<usize>> {
+        let regex = Regex::new(&self.from).expect("regex should be valid");
+        let skipped = self.skipped_ranges(content);
+        regex
+            .find_iter(content)
+            .map(|found| found.range())
+            .filter(|range| !overlaps_any(range, &skipped))
+            .collect()
+    }
+
+    /// Apply the replacement to the content.
+    pub fn replace<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        let regex = Regex::new(&self.from).expect("regex should be valid");
+        if self.skip.is_none() {
+            return regex.replace_all(content, self.to.as_str());
+        }
+
+        let skipped = self.skipped_ranges(content);
+        let mut replaced = String::with_capacity(content.len());
+        let mut last = 0;
+        for captures in regex.captures_iter(content) {
+            let found = captures.get(0).expect("match should exist");
+            if overlaps_any(&found.range(), &skipped) {
+                continue;
+            }
+            replaced.push_str(&content[last..found.start()]);
+            captures.expand(&self.to, &mut replaced);
+            last = found.end();
         }
+        replaced.push_str(&content[last..]);
+        Cow::Owned(replaced)
     }
+
+    fn skipped_ranges(&self, content: &str) -> Vec<Range<usize>> {
+        self.skip
+            .as_ref()
+            .map(|skip| {
+                Regex::new(skip)
+                    .expect("regex should be valid")
+                    .find_iter(content)
+                    .map(|found| found.range())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn overlaps_any(range: &Range<usize>, others: &[Range<usize>]) -> bool {
+    others
+        .iter()
+        .any(|other| range.start < other.end && other.start < range.end)
 }
 
 impl Display for ReplaceInFile {
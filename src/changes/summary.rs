@@ -0,0 +1,101 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::encoding::read_to_string;
+
+use super::Change;
+
+/// File name suffixes that identify a file type better than the extension.
+const COMPOUND_SUFFIXES: &[&str] = &["Build.cs", "Target.cs"];
+
+/// Group a changeset by the type of file that each change applies to, e.g.
+/// `cpp` or `Build.cs`. Changes to folders are grouped as `folder`. Groups
+/// are sorted by file type and keep the order of the changeset.
+pub fn group_by_file_type(changeset: &[Change]) -> Vec<(String, Vec<&Change>)> {
+    let mut groups: BTreeMap<String, Vec<&Change>> = BTreeMap::new();
+    for change in changeset {
+        groups
+            .entry(file_type(change.path()))
+            .or_default()
+            .push(change);
+    }
+    groups.into_iter().collect()
+}
+
+/// The type of a file given its path, e.g. `cpp` or `Build.cs`. Paths
+/// without an extension are assumed to be folders.
+pub fn file_type(path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    if let Some(suffix) = COMPOUND_SUFFIXES
+        .iter()
+        .find(|suffix| file_name.ends_with(&format!(".{}", suffix)))
+    {
+        return suffix.to_string();
+    }
+    match path.extension() {
+        Some(extension) => extension.to_string_lossy().into_owned(),
+        None => "folder".into(),
+    }
+}
+
+/// Preview a change as lines to show to the user. Replacements in text
+/// files are previewed as a diff of the affected lines, any other change as
+/// its description.
+pub fn preview(change: &Change) -> Vec<String> {
+    let params = match change {
+        Change::ReplaceInFile(params) => params,
+        change => return vec![change.to_string()],
+    };
+    match read_to_string(&params.path) {
+        Ok(before) => {
+            let after = params.replace(&before);
+            let mut lines = vec![change.to_string()];
+            lines.extend(diff_lines(&before, &after));
+            lines
+        }
+        Err(err) => vec![change.to_string(), err.to_string()],
+    }
+}
+
+/// Diff two versions of a text line by line, listing removed lines with `-`
+/// and added lines with `+`, prefixed by their line number.
+pub fn diff_lines(before: &str, after: &str) -> Vec<String> {
+    let before = before.lines().collect::<Vec<_>>();
+    let after = after.lines().collect::<Vec<_>>();
+    if before.len() == after.len() {
+        return before
+            .iter()
+            .zip(&after)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .flat_map(|(index, (old, new))| {
+                vec![
+                    format!("-{:>5} {}", index + 1, old),
+                    format!("+{:>5} {}", index + 1, new),
+                ]
+            })
+            .collect();
+    }
+    let prefix = before
+        .iter()
+        .zip(&after)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let removed = before[prefix..before.len() - suffix]
+        .iter()
+        .enumerate()
+        .map(|(index, line)| format!("-{:>5} {}", prefix + index + 1, line));
+    let added = after[prefix..after.len() - suffix]
+        .iter()
+        .enumerate()
+        .map(|(index, line)| format!("+{:>5} {}", prefix + index + 1, line));
+    removed.chain(added).collect()
+}
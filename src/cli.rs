@@ -1,8 +1,17 @@
-use std::path::PathBuf;
+use std::{convert::TryFrom, env, path::PathBuf};
 
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand};
 
-use crate::workflows::{rename_module, rename_plugin, rename_project, rename_target};
+use crate::{
+    detection::detect_project_root,
+    installation::EngineOptions,
+    vcs::{Vcs, VcsOptions},
+    workflows::{
+        backup, clean, doctor, graph, inspect, parse_age, refs, rename_content_folder,
+        rename_module, rename_plugin, rename_project, rename_target, BackupAction, GraphFormat,
+        InspectFormat,
+    },
+};
 
 #[derive(Parser)]
 #[command(author, version, about, arg_required_else_help(true))]
@@ -21,94 +30,596 @@ pub enum Command {
     RenameTarget(RenameTarget),
     /// Rename an Unreal Engine project module
     RenameModule(RenameModule),
+    /// Rename a top-level Unreal Engine project content folder
+    RenameContentFolder(RenameContentFolder),
     /// Start an interactive session
     Wizard,
+    /// Print the project structure as detected by Renom
+    Inspect(Inspect),
+    /// Export the module and plugin dependency graph of a project
+    Graph(Graph),
+    /// Check a project for inconsistencies left behind by incomplete renames
+    Doctor(Doctor),
+    /// Find every reference to a module, plugin or target in a project
+    Refs(Refs),
+    /// List, prune or restore the backups made by previous operations
+    Backup(Backup),
+    /// Remove generated files, such as binaries and intermediates, from a project
+    Clean(Clean),
 }
 
 #[derive(PartialEq, Debug, Parser)]
 pub struct RenameProject {
-    /// Path to the project to rename
+    /// Path to the project to rename, defaults to the project in the current directory
     #[arg(long)]
-    project: PathBuf,
+    project: Option<PathBuf>,
     /// New name for the project
     #[arg(long)]
-    new_name: String,
+    new_name: Option<String>,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    /// Remove generated files, such as binaries and intermediates, that the rename leaves stale
+    #[arg(long)]
+    clean: bool,
+    /// Fail instead of prompting for missing arguments
+    #[arg(long)]
+    no_input: bool,
+    #[command(flatten)]
+    vcs: VcsArgs,
+    #[command(flatten)]
+    engine: EngineArgs,
 }
 
-impl From<RenameProject> for rename_project::Params {
-    fn from(params: RenameProject) -> Self {
-        Self {
-            project_root: params.project,
-            new_name: params.new_name,
+impl RenameProject {
+    /// Fill in missing arguments, taking the project from the current
+    /// directory and prompting for the rest unless `--no-input` is given.
+    pub fn resolve_missing_args(mut self) -> Result<Self, String> {
+        self.project = project_root_or_current(self.project);
+        if self.no_input {
+            require_args(&[
+                ("project", self.project.is_none()),
+                ("new-name", self.new_name.is_none()),
+            ])?;
         }
+        let params = rename_project::Params::from_user(
+            self.project,
+            self.new_name,
+            self.engine.clone().into(),
+        )?;
+        self.project = Some(params.project_root);
+        self.new_name = Some(params.new_name);
+        Ok(self)
+    }
+}
+
+impl TryFrom<RenameProject> for rename_project::Params {
+    type Error = String;
+
+    fn try_from(params: RenameProject) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: required(params.project, "project")?,
+            new_name: required(params.new_name, "new-name")?,
+            backup_dir: params.backup_dir,
+            clean: params.clean,
+            vcs: params.vcs.into(),
+            engine: params.engine.into(),
+        })
     }
 }
 
 #[derive(PartialEq, Debug, Parser)]
 pub struct RenamePlugin {
-    /// Path to the project that the plugin is part of
+    /// Path to the project that the plugin is part of, defaults to the project in the current directory
     #[arg(long)]
-    project: PathBuf,
+    project: Option<PathBuf>,
     /// Plugin in the project to rename
     #[arg(long)]
-    plugin: String,
+    plugin: Option<String>,
     /// New name for the plugin
     #[arg(long)]
-    new_name: String,
+    new_name: Option<String>,
+    /// Report assets that reference the plugin
+    #[arg(long)]
+    scan_assets: bool,
+    /// Rewrite references to the plugin in assets in place; the new name must have the same
+    /// length, since reserializing assets is not supported
+    #[arg(long)]
+    rewrite_assets: bool,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    /// Remove generated files, such as binaries and intermediates, that the rename leaves stale
+    #[arg(long)]
+    clean: bool,
+    /// Fail instead of prompting for missing arguments
+    #[arg(long)]
+    no_input: bool,
+    #[command(flatten)]
+    vcs: VcsArgs,
+    #[command(flatten)]
+    engine: EngineArgs,
 }
 
-impl From<RenamePlugin> for rename_plugin::Params {
-    fn from(params: RenamePlugin) -> Self {
-        Self {
-            project_root: params.project,
-            plugin: params.plugin,
-            new_name: params.new_name,
+impl RenamePlugin {
+    /// Fill in missing arguments, taking the project from the current
+    /// directory and prompting for the rest unless `--no-input` is given.
+    pub fn resolve_missing_args(mut self) -> Result<Self, String> {
+        self.project = project_root_or_current(self.project);
+        if self.no_input {
+            require_args(&[
+                ("project", self.project.is_none()),
+                ("plugin", self.plugin.is_none()),
+                ("new-name", self.new_name.is_none()),
+            ])?;
         }
+        let params = rename_plugin::Params::from_user(
+            self.project,
+            self.plugin,
+            self.new_name,
+            self.engine.clone().into(),
+        )?;
+        self.project = Some(params.project_root);
+        self.plugin = Some(params.plugin);
+        self.new_name = Some(params.new_name);
+        Ok(self)
+    }
+}
+
+impl TryFrom<RenamePlugin> for rename_plugin::Params {
+    type Error = String;
+
+    fn try_from(params: RenamePlugin) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: required(params.project, "project")?,
+            plugin: required(params.plugin, "plugin")?,
+            new_name: required(params.new_name, "new-name")?,
+            scan_assets: params.scan_assets,
+            rewrite_assets: params.rewrite_assets,
+            backup_dir: params.backup_dir,
+            clean: params.clean,
+            vcs: params.vcs.into(),
+            engine: params.engine.into(),
+        })
     }
 }
 
 #[derive(PartialEq, Debug, Parser)]
 pub struct RenameTarget {
-    /// Path to the project that the target is part of
+    /// Path to the project that the target is part of, defaults to the project in the current directory
     #[arg(long)]
-    project: PathBuf,
+    project: Option<PathBuf>,
     /// Target in the project to rename
     #[arg(long)]
-    target: String,
+    target: Option<String>,
     /// New name for the target
     #[arg(long)]
-    new_name: String,
+    new_name: Option<String>,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    /// Remove generated files, such as binaries and intermediates, that the rename leaves stale
+    #[arg(long)]
+    clean: bool,
+    /// Fail instead of prompting for missing arguments
+    #[arg(long)]
+    no_input: bool,
+    #[command(flatten)]
+    vcs: VcsArgs,
+    #[command(flatten)]
+    engine: EngineArgs,
 }
 
-impl From<RenameTarget> for rename_target::Params {
-    fn from(params: RenameTarget) -> Self {
-        Self {
-            project_root: params.project,
-            target: params.target,
-            new_name: params.new_name,
+impl RenameTarget {
+    /// Fill in missing arguments, taking the project from the current
+    /// directory and prompting for the rest unless `--no-input` is given.
+    pub fn resolve_missing_args(mut self) -> Result<Self, String> {
+        self.project = project_root_or_current(self.project);
+        if self.no_input {
+            require_args(&[
+                ("project", self.project.is_none()),
+                ("target", self.target.is_none()),
+                ("new-name", self.new_name.is_none()),
+            ])?;
         }
+        let params = rename_target::Params::from_user(
+            self.project,
+            self.target,
+            self.new_name,
+            self.engine.clone().into(),
+        )?;
+        self.project = Some(params.project_root);
+        self.target = Some(params.target);
+        self.new_name = Some(params.new_name);
+        Ok(self)
+    }
+}
+
+impl TryFrom<RenameTarget> for rename_target::Params {
+    type Error = String;
+
+    fn try_from(params: RenameTarget) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: required(params.project, "project")?,
+            target: required(params.target, "target")?,
+            new_name: required(params.new_name, "new-name")?,
+            backup_dir: params.backup_dir,
+            clean: params.clean,
+            vcs: params.vcs.into(),
+            engine: params.engine.into(),
+        })
     }
 }
 
 #[derive(PartialEq, Debug, Parser)]
 pub struct RenameModule {
-    /// Path to the project that the module is part of
+    /// Path to the project that the module is part of, defaults to the project in the current directory
     #[arg(long)]
-    project: PathBuf,
+    project: Option<PathBuf>,
     /// Module in the project to rename
     #[arg(long)]
-    module: String,
+    module: Option<String>,
     /// New name for the module
     #[arg(long)]
-    new_name: String,
+    new_name: Option<String>,
+    /// Report assets that reference the module
+    #[arg(long)]
+    scan_assets: bool,
+    /// Rewrite references to the module in assets in place; the new name must have the same
+    /// length, since reserializing assets is not supported
+    #[arg(long)]
+    rewrite_assets: bool,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    /// Remove generated files, such as binaries and intermediates, that the rename leaves stale
+    #[arg(long)]
+    clean: bool,
+    /// Fail instead of prompting for missing arguments
+    #[arg(long)]
+    no_input: bool,
+    #[command(flatten)]
+    vcs: VcsArgs,
+    #[command(flatten)]
+    engine: EngineArgs,
+}
+
+impl RenameModule {
+    /// Fill in missing arguments, taking the project from the current
+    /// directory and prompting for the rest unless `--no-input` is given.
+    pub fn resolve_missing_args(mut self) -> Result<Self, String> {
+        self.project = project_root_or_current(self.project);
+        if self.no_input {
+            require_args(&[
+                ("project", self.project.is_none()),
+                ("module", self.module.is_none()),
+                ("new-name", self.new_name.is_none()),
+            ])?;
+        }
+        let params = rename_module::Params::from_user(
+            self.project,
+            self.module,
+            self.new_name,
+            self.engine.clone().into(),
+        )?;
+        self.project = Some(params.project_root);
+        self.module = Some(params.module);
+        self.new_name = Some(params.new_name);
+        Ok(self)
+    }
+}
+
+impl TryFrom<RenameModule> for rename_module::Params {
+    type Error = String;
+
+    fn try_from(params: RenameModule) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: required(params.project, "project")?,
+            module: required(params.module, "module")?,
+            new_name: required(params.new_name, "new-name")?,
+            scan_assets: params.scan_assets,
+            rewrite_assets: params.rewrite_assets,
+            backup_dir: params.backup_dir,
+            clean: params.clean,
+            vcs: params.vcs.into(),
+            engine: params.engine.into(),
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct RenameContentFolder {
+    /// Path to the project that the content folder is part of, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Top-level folder in the project Content folder to rename
+    #[arg(long)]
+    folder: Option<String>,
+    /// New name for the content folder
+    #[arg(long)]
+    new_name: Option<String>,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    /// Fail instead of prompting for missing arguments
+    #[arg(long)]
+    no_input: bool,
+    #[command(flatten)]
+    vcs: VcsArgs,
+}
+
+impl RenameContentFolder {
+    /// Fill in missing arguments, taking the project from the current
+    /// directory and prompting for the rest unless `--no-input` is given.
+    pub fn resolve_missing_args(mut self) -> Result<Self, String> {
+        self.project = project_root_or_current(self.project);
+        if self.no_input {
+            require_args(&[
+                ("project", self.project.is_none()),
+                ("folder", self.folder.is_none()),
+                ("new-name", self.new_name.is_none()),
+            ])?;
+        }
+        let params =
+            rename_content_folder::Params::from_user(self.project, self.folder, self.new_name)?;
+        self.project = Some(params.project_root);
+        self.folder = Some(params.folder);
+        self.new_name = Some(params.new_name);
+        Ok(self)
+    }
+}
+
+impl TryFrom<RenameContentFolder> for rename_content_folder::Params {
+    type Error = String;
+
+    fn try_from(params: RenameContentFolder) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: required(params.project, "project")?,
+            folder: required(params.folder, "folder")?,
+            new_name: required(params.new_name, "new-name")?,
+            backup_dir: params.backup_dir,
+            vcs: params.vcs.into(),
+        })
+    }
+}
+
+/// The given project root or, failing that, the root of the project that the
+/// current directory is part of.
+fn project_root_or_current(project: Option<PathBuf>) -> Option<PathBuf> {
+    project.or_else(|| {
+        env::current_dir()
+            .ok()
+            .and_then(|current_dir| detect_project_root(&current_dir))
+    })
+}
+
+/// Like [`project_root_or_current`], but fails when there is no project.
+fn require_project_root(project: Option<PathBuf>) -> Result<PathBuf, String> {
+    project_root_or_current(project).ok_or_else(|| {
+        "could not find a project in the current directory or its parents, pass --project".into()
+    })
+}
+
+/// Take an argument that is required once missing arguments are resolved.
+fn required<T>(value: Option<T>, name: &str) -> Result<T, String> {
+    value.ok_or_else(|| format!("--{} is required", name))
+}
+
+/// Fail for the first argument that is missing, given as a pair of its name
+/// and whether it is missing.
+fn require_args(args: &[(&str, bool)]) -> Result<(), String> {
+    match args.iter().find(|(_, missing)| *missing) {
+        Some((name, _)) => Err(format!("--{} is required with --no-input", name)),
+        None => Ok(()),
+    }
+}
+
+#[derive(PartialEq, Debug, Args)]
+pub struct VcsArgs {
+    /// Version control system of the project, used to stage renames and to check for uncommitted changes
+    #[arg(long, value_enum)]
+    vcs: Option<Vcs>,
+    /// Warn instead of failing when the project has uncommitted changes
+    #[arg(long, requires = "vcs")]
+    allow_dirty: bool,
+    /// Commit the changes with a message that summarizes them
+    #[arg(long, requires = "vcs")]
+    commit: bool,
+    /// Clear read-only flags on files before changing them
+    #[arg(long)]
+    clear_read_only: bool,
 }
 
-impl From<RenameModule> for rename_module::Params {
-    fn from(params: RenameModule) -> Self {
+impl From<VcsArgs> for VcsOptions {
+    fn from(args: VcsArgs) -> Self {
         Self {
-            project_root: params.project,
-            module: params.module,
-            new_name: params.new_name,
+            vcs: args.vcs,
+            allow_dirty: args.allow_dirty,
+            commit: args.commit,
+            clear_read_only: args.clear_read_only,
         }
     }
 }
+
+#[derive(Clone, PartialEq, Debug, Args)]
+pub struct EngineArgs {
+    /// Root of the engine to check new names against, defaults to the engine associated with the project
+    #[arg(long)]
+    engine_root: Option<PathBuf>,
+    /// Launcher installed list or Install.ini file to resolve the engine association of the project with
+    #[arg(long, conflicts_with = "engine_root")]
+    engine_registry: Option<PathBuf>,
+}
+
+impl From<EngineArgs> for EngineOptions {
+    fn from(args: EngineArgs) -> Self {
+        Self {
+            engine_root: args.engine_root,
+            engine_registry: args.engine_registry,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct Inspect {
+    /// Path to the project to inspect, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Format to print the project structure in
+    #[arg(long, value_enum, default_value_t = InspectFormat::Text)]
+    format: InspectFormat,
+}
+
+impl TryFrom<Inspect> for inspect::Params {
+    type Error = String;
+
+    fn try_from(params: Inspect) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+            format: params.format,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct Graph {
+    /// Path to the project to export the dependency graph for, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Format to export the dependency graph in
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    format: GraphFormat,
+}
+
+impl TryFrom<Graph> for graph::Params {
+    type Error = String;
+
+    fn try_from(params: Graph) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+            format: params.format,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct Doctor {
+    /// Path to the project to check, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Apply fixes for the issues that can be fixed automatically
+    #[arg(long)]
+    fix: bool,
+    /// Directory to store backups in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+}
+
+impl TryFrom<Doctor> for doctor::Params {
+    type Error = String;
+
+    fn try_from(params: Doctor) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+            fix: params.fix,
+            backup_dir: params.backup_dir,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+#[command(group(ArgGroup::new("subject").required(true).args(["module", "plugin", "target"])))]
+pub struct Refs {
+    /// Path to the project to search, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Module in the project to find references to
+    #[arg(long)]
+    module: Option<String>,
+    /// Plugin in the project to find references to
+    #[arg(long)]
+    plugin: Option<String>,
+    /// Target in the project to find references to
+    #[arg(long)]
+    target: Option<String>,
+}
+
+impl TryFrom<Refs> for refs::Params {
+    type Error = String;
+
+    fn try_from(params: Refs) -> Result<Self, Self::Error> {
+        let subject = match (params.module, params.plugin, params.target) {
+            (Some(module), _, _) => refs::Subject::Module(module),
+            (_, Some(plugin), _) => refs::Subject::Plugin(plugin),
+            (_, _, Some(target)) => refs::Subject::Target(target),
+            _ => unreachable!("clap should require one of module, plugin or target"),
+        };
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+            subject,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct Clean {
+    /// Path to the project to clean, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+}
+
+impl TryFrom<Clean> for clean::Params {
+    type Error = String;
+
+    fn try_from(params: Clean) -> Result<Self, Self::Error> {
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Parser)]
+pub struct Backup {
+    /// Path to the project whose backups to manage, defaults to the project in the current directory
+    #[arg(long)]
+    project: Option<PathBuf>,
+    /// Directory that backups are stored in, defaults to a per-user data directory
+    #[arg(long)]
+    backup_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: BackupCommand,
+}
+
+#[derive(PartialEq, Debug, Subcommand)]
+pub enum BackupCommand {
+    /// List the backup sets of the project
+    List,
+    /// Remove backup sets older than a given age
+    Prune {
+        /// Age of the backup sets to remove, e.g. 30d, 12h or 45m
+        #[arg(long, value_parser = parse_age)]
+        older_than: chrono::Duration,
+    },
+    /// Restore every file in a backup set
+    Restore {
+        /// Id of the backup set to restore
+        id: String,
+    },
+}
+
+impl TryFrom<Backup> for backup::Params {
+    type Error = String;
+
+    fn try_from(params: Backup) -> Result<Self, Self::Error> {
+        let action = match params.command {
+            BackupCommand::List => BackupAction::List,
+            BackupCommand::Prune { older_than } => BackupAction::Prune { older_than },
+            BackupCommand::Restore { id } => BackupAction::Restore { id },
+        };
+        Ok(Self {
+            project_root: require_project_root(params.project)?,
+            backup_dir: params.backup_dir,
+            action,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! Per-user configuration of Renom, such as the projects that the wizard was
+//! recently used with. The configuration is stored as TOML in the per-user
+//! config directory.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How many recent projects to remember.
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+/// The per-user configuration.
+#[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+pub struct Config {
+    /// Absolute paths of recently used projects, most recent first.
+    #[serde(default)]
+    pub recent_projects: Vec<PathBuf>,
+}
+
+impl Config {
+    /// Load the configuration from a file, falling back to the defaults if
+    /// the file does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        toml::from_str(&content)
+            .map_err(|err| format!("config {} is not valid TOML: {}", path.display(), err))
+    }
+
+    /// Save the configuration to a file, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|err| err.to_string())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        fs::write(path, content).map_err(|err| err.to_string())
+    }
+
+    /// Move a project to the front of the recent projects, forgetting the
+    /// least recent project if there are too many.
+    pub fn add_recent_project(&mut self, project: &Path) -> Result<(), String> {
+        let project = match project.is_absolute() {
+            true => project.to_owned(),
+            false => env::current_dir()
+                .map_err(|err| err.to_string())?
+                .join(project),
+        };
+        self.recent_projects.retain(|recent| recent != &project);
+        self.recent_projects.insert(0, project);
+        self.recent_projects.truncate(MAX_RECENT_PROJECTS);
+        Ok(())
+    }
+
+    /// The recent projects that still exist, most recent first.
+    pub fn existing_recent_projects(&self) -> Vec<PathBuf> {
+        self.recent_projects
+            .iter()
+            .filter(|project| project.exists())
+            .cloned()
+            .collect()
+    }
+}
+
+/// The path of the configuration file in the per-user config directory:
+/// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS and
+/// `$XDG_CONFIG_HOME` or `~/.config` elsewhere.
+pub fn config_path() -> Result<PathBuf, String> {
+    let var = |name| {
+        env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let config_dir = if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+    };
+    config_dir
+        .map(|config_dir| config_dir.join("renom").join("config.toml"))
+        .ok_or_else(|| "could not locate the user config directory".into())
+}
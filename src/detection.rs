@@ -0,0 +1,425 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::{
+    encoding::read_to_string,
+    unreal::{Module, ModuleDependency, ModuleType, Plugin, Target, TargetType},
+};
+
+/// Detect the project descriptor given the path to either the project root
+/// directory or the descriptor itself. Returns an error if the directory
+/// cannot be read or does not contain exactly one project descriptor.
+pub fn detect_project_descriptor(project: &Path) -> Result<PathBuf, String> {
+    if is_project_descriptor(project) {
+        return Ok(project.to_owned());
+    }
+    let mut descriptors = find_project_descriptors(project)?;
+    match descriptors.len() {
+        0 => Err("project root must contain a project descriptor".into()),
+        1 => Ok(descriptors.remove(0)),
+        _ => Err(format!(
+            "project root contains several project descriptors ({}), pass the path to one of them instead",
+            descriptors
+                .iter()
+                .filter_map(|descriptor| descriptor.file_name()?.to_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Find all project descriptors in a directory, sorted by path.
+pub fn find_project_descriptors(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut descriptors = fs::read_dir(dir)
+        .map_err(|err| err.to_string())?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| is_project_descriptor(path))
+        .collect::<Vec<_>>();
+    descriptors.sort();
+    Ok(descriptors)
+}
+
+/// Get the project root directory given the path to either the project root
+/// directory or the project descriptor.
+pub fn project_root_of(project: &Path) -> PathBuf {
+    match is_project_descriptor(project) {
+        true => match project.parent() {
+            Some(parent) if parent != Path::new("") => parent.to_owned(),
+            _ => PathBuf::from("."),
+        },
+        false => project.to_owned(),
+    }
+}
+
+fn is_project_descriptor(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "uproject")
+}
+
+/// Detect the root of the project that a directory is part of, which is the
+/// closest ancestor of the directory (or the directory itself) that contains
+/// a project descriptor.
+pub fn detect_project_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| find_project_descriptors(dir).is_ok_and(|found| !found.is_empty()))
+        .map(Path::to_owned)
+}
+
+/// Detect the name of a project given the path to either the project root
+/// directory or the project descriptor. Returns an error if the descriptor
+/// cannot be determined.
+pub fn detect_project_name(project: &Path) -> Result<String, String> {
+    let project_descriptor = detect_project_descriptor(project)?;
+    project_descriptor
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|name| name.to_owned())
+        .ok_or_else(|| "project name is not valid Unicode".into())
+}
+
+/// Detect the engine that a project is associated with, given the path to
+/// either the project root directory or the project descriptor. The
+/// association is either an engine version (for installed engines) or an
+/// identifier (for source builds). Returns `None` for projects without an
+/// association.
+pub fn detect_engine_association(project: &Path) -> Result<Option<String>, String> {
+    let descriptor = read_descriptor(&detect_project_descriptor(project)?)?;
+    Ok(descriptor
+        .get("EngineAssociation")
+        .and_then(Value::as_str)
+        .filter(|association| !association.is_empty())
+        .map(str::to_owned))
+}
+
+/// Detect the host type (e.g. Runtime, Editor) of each module listed in a
+/// project or plugin descriptor. Returns a map from module name to host type.
+pub fn detect_module_host_types(descriptor: &Path) -> Result<HashMap<String, String>, String> {
+    let descriptor = read_descriptor(descriptor)?;
+    Ok(descriptor
+        .get("Modules")
+        .and_then(Value::as_array)
+        .map(|modules| {
+            modules
+                .iter()
+                .filter_map(|module| {
+                    let name = module.get("Name")?.as_str()?;
+                    let host_type = module.get("Type")?.as_str()?;
+                    Some((name.to_owned(), host_type.to_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn read_descriptor(descriptor: &Path) -> Result<Value, String> {
+    let content = read_to_string(descriptor).map_err(|err| err.to_string())?;
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "descriptor {} is not valid JSON: {}",
+            descriptor.display(),
+            err
+        )
+    })
+}
+
+/// Detect all plugins in a project given the path to the project root
+/// directory. Detects top-level plugins and nested plugins. Returns an error in
+/// case of I/O issues.
+pub fn detect_project_plugins(project_root: &Path) -> Result<Vec<Plugin>, String> {
+    let plugins_dir = project_root.join("Plugins");
+    Ok(WalkDir::new(plugins_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "uplugin"))
+        .map(|entry| Plugin {
+            root: entry.path().parent().unwrap().to_owned(),
+            name: entry
+                .path()
+                .file_stem()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_owned(),
+        })
+        .collect())
+}
+
+/// Detect all project modules in a project given the path to the project root
+/// directory. Detects top-level modules and nested modules. Assumes that the
+/// Source folder exists. Returns an error in case of I/O issues.
+pub fn detect_project_modules(project_root: &Path) -> Result<Vec<Module>, String> {
+    let source_dir = project_root.join("Source");
+    assert!(source_dir.is_dir());
+    Ok(WalkDir::new(source_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir() && dir_contains_module_descriptor(entry.path()))
+        .map(|entry| Module {
+            root: entry.path().to_owned(),
+            name: get_dir_name(entry.path()),
+            r#type: ModuleType::Project,
+            plugin: None,
+        })
+        .collect())
+}
+
+/// Detect all plugin modules in a project given the list of project plugins.
+/// Detects top-level modules and nested modules. Returns an error in case of
+/// I/O issues.
+pub fn detect_plugin_modules(project_plugins: &[Plugin]) -> Result<Vec<Module>, String> {
+    Ok(project_plugins
+        .iter()
+        .flat_map(|plugin| {
+            WalkDir::new(&plugin.root)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry.path().is_dir() && dir_contains_module_descriptor(entry.path())
+                })
+                .map(move |entry| Module {
+                    root: entry.path().to_owned(),
+                    name: get_dir_name(entry.path()),
+                    r#type: ModuleType::Plugin,
+                    plugin: Some(plugin.clone()),
+                })
+        })
+        .collect())
+}
+
+/// Detect all modules in a project, both project modules and plugin modules.
+/// Assumes that the Source folder exists. Returns an error in case of I/O
+/// issues.
+pub fn detect_all_modules(project_root: &Path) -> Result<Vec<Module>, String> {
+    let project_plugins = detect_project_plugins(project_root)?;
+    Ok(detect_project_modules(project_root)?
+        .into_iter()
+        .chain(detect_plugin_modules(&project_plugins)?)
+        .collect())
+}
+
+/// Whether a module belongs to the closest plugin that contains it. Modules
+/// of nested plugins are detected once for each plugin that contains them,
+/// so this keeps only the detection under the plugin that they belong to.
+pub fn is_in_closest_plugin(module: &Module, plugins: &[Plugin]) -> bool {
+    let closest = plugins
+        .iter()
+        .filter(|plugin| module.root.starts_with(&plugin.root))
+        .max_by_key(|plugin| plugin.root.components().count());
+    match (&module.plugin, closest) {
+        (Some(plugin), Some(closest)) => plugin.root == closest.root,
+        _ => true,
+    }
+}
+
+/// Detect all build targets in a project given the path to the project root
+/// directory. Only top-level targets in the Source folder are detected.
+/// Assumes that the Source folder exists. Returns an error in case of I/O
+/// issues.
+pub fn detect_project_targets(project_root: &Path) -> Result<Vec<Target>, String> {
+    let source_dir = project_root.join("Source");
+    assert!(source_dir.is_dir());
+    Ok(fs::read_dir(&source_dir)
+        .map_err(|err| err.to_string())?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry
+                .path()
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|str| str.strip_suffix(".Target.cs"))
+                .map(|str| str.to_owned())
+        })
+        .map(|target_name| {
+            let path = source_dir.join(&target_name).with_extension("Target.cs");
+            Target {
+                name: target_name,
+                r#type: detect_target_type(&path),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Detect the names of all modules and plugins in a project given the path
+/// to the project root directory. Modules are referenced by name from build
+/// files and plugin descriptors alike, so a new module or plugin name must
+/// not collide with any of them. Assumes that the Source folder exists.
+/// Returns an error in case of I/O issues.
+pub fn detect_module_and_plugin_names(project_root: &Path) -> Result<Vec<String>, String> {
+    let plugins = detect_project_plugins(project_root)?;
+    Ok(detect_all_modules(project_root)?
+        .into_iter()
+        .map(|module| module.name)
+        .chain(plugins.into_iter().map(|plugin| plugin.name))
+        .collect())
+}
+
+/// Detect the type of a target given the path to its target file. Returns
+/// `None` if the type is not declared or cannot be read.
+pub fn detect_target_type(target_file: &Path) -> Option<TargetType> {
+    let content = read_to_string(target_file).ok()?;
+    let (_, rest) = content.split_once("TargetType.")?;
+    let type_name: String = rest
+        .chars()
+        .take_while(|char| char.is_ascii_alphanumeric())
+        .collect();
+    type_name.parse().ok()
+}
+
+/// Detect all config files in a project given the path to the project root
+/// directory. Detects top-level and platform-specific config files of the
+/// project and its plugins, including those in platform extension folders.
+pub fn detect_project_config_files(project_root: &Path) -> Result<Vec<PathBuf>, String> {
+    let roots = std::iter::once(project_root.to_owned()).chain(
+        detect_project_plugins(project_root)?
+            .into_iter()
+            .map(|plugin| plugin.root),
+    );
+    let mut config_dirs = vec![];
+    for root in roots {
+        config_dirs.push(root.join("Config"));
+        if let Ok(platforms) = fs::read_dir(root.join("Platforms")) {
+            config_dirs.extend(
+                platforms
+                    .filter_map(Result::ok)
+                    .map(|platform| platform.path().join("Config")),
+            );
+        }
+    }
+    Ok(config_dirs
+        .into_iter()
+        .flat_map(|config_dir| {
+            WalkDir::new(config_dir)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "ini"))
+                .map(|entry| entry.path().to_owned())
+        })
+        .collect())
+}
+
+/// Detect all content folders in a project given the path to the project
+/// root directory. Detects the project content folder and the content
+/// folders of all project plugins. Returns an error in case of I/O issues.
+pub fn detect_content_dirs(project_root: &Path) -> Result<Vec<PathBuf>, String> {
+    Ok(std::iter::once(project_root.to_owned())
+        .chain(
+            detect_project_plugins(project_root)?
+                .into_iter()
+                .map(|plugin| plugin.root),
+        )
+        .map(|root| root.join("Content"))
+        .filter(|content_dir| content_dir.is_dir())
+        .collect())
+}
+
+/// Detect the top-level folders in the content folder of a project given the
+/// path to the project root directory. Returns an error in case of I/O issues.
+pub fn detect_content_folders(project_root: &Path) -> Result<Vec<String>, String> {
+    let mut folders = fs::read_dir(project_root.join("Content"))
+        .map_err(|err| err.to_string())?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_owned))
+        .collect::<Vec<_>>();
+    folders.sort();
+    Ok(folders)
+}
+
+/// Detect the public and private dependencies of a module by parsing its
+/// build file. Commented out dependencies are ignored. Returns an error in
+/// case of I/O issues.
+pub fn detect_module_dependencies(module: &Module) -> Result<Vec<ModuleDependency>, String> {
+    let build_file = module.root.join(&module.name).with_extension("Build.cs");
+    let content = read_to_string(&build_file).map_err(|err| err.to_string())?;
+    let content = strip_comments(&content);
+    let dependency_list_regex = Regex::new(
+        r#"(?P<visibility>Public|Private)DependencyModuleNames\s*\.\s*Add(Range)?\s*\((?P<list>[^;]*?)\)\s*;"#,
+    )
+    .expect("regex should be valid");
+    let string_literal_regex = Regex::new(r#""(?P<name>[^"]+)""#).expect("regex should be valid");
+
+    Ok(dependency_list_regex
+        .captures_iter(&content)
+        .flat_map(|captures| {
+            let public = &captures["visibility"] == "Public";
+            string_literal_regex
+                .captures_iter(captures.name("list").unwrap().as_str())
+                .map(move |literal| ModuleDependency {
+                    name: literal["name"].to_owned(),
+                    public,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+/// Detect the plugins that a plugin depends on, as listed in its descriptor.
+pub fn detect_plugin_dependencies(plugin: &Plugin) -> Result<Vec<String>, String> {
+    let descriptor = read_descriptor(&plugin.root.join(&plugin.name).with_extension("uplugin"))?;
+    Ok(descriptor
+        .get("Plugins")
+        .and_then(Value::as_array)
+        .map(|plugins| {
+            plugins
+                .iter()
+                .filter_map(|plugin| plugin.get("Name")?.as_str())
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Strip line and block comments from C-like source code.
+fn strip_comments(content: &str) -> String {
+    let comment_regex = Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").expect("regex should be valid");
+    comment_regex.replace_all(content, "").into_owned()
+}
+
+/// Find the source file in a module that contains the module implement macro.
+pub fn find_implementing_source(module_root: &Path) -> Option<PathBuf> {
+    WalkDir::new(module_root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().to_owned())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "cpp"))
+        .find(|source| read_to_string(source).is_ok_and(|content| content.contains("_MODULE")))
+}
+
+/// Find the files in a module that use the module export macro.
+pub fn find_headers_with_export_macro(module_root: &Path, module_name: &str) -> Vec<PathBuf> {
+    WalkDir::new(module_root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().to_owned())
+        .filter(|path| {
+            read_to_string(path).is_ok_and(|content| content.contains(&export_macro(module_name)))
+        })
+        .collect()
+}
+
+/// Get the export macro for a module, e.g. `MYMODULE_API`.
+pub fn export_macro(module_name: &str) -> String {
+    format!("{}_API", module_name.to_uppercase())
+}
+
+fn dir_contains_module_descriptor(dir: &Path) -> bool {
+    assert!(dir.is_dir());
+    let dir_name = dir.file_name().expect("directory name should exist");
+    dir.join(dir_name).with_extension("Build.cs").is_file()
+}
+
+fn get_dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .expect("directory name should exist")
+        .to_str()
+        .expect("name should be valid Unicode")
+        .to_string()
+}
@@ -0,0 +1,207 @@
+//! Detection and round-tripping of text file encodings and line endings, so
+//! that changes to a file preserve its original format.
+
+use std::{convert::TryFrom, io, path::Path};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Extensions of the text files of a project whose encoding is guessed when
+/// they have no byte order mark and are not valid UTF-8. Other files, such
+/// as packages, are binary and fail to decode instead.
+const TEXT_EXTENSIONS: &[&str] = &["ini", "cs", "h", "cpp", "uproject", "uplugin"];
+
+/// The encoding of a text file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Encoding {
+    /// UTF-8 without a byte order mark.
+    Utf8,
+    /// UTF-8 with a byte order mark.
+    Utf8Bom,
+    /// UTF-16 little endian with a byte order mark, as written by Unreal
+    /// Engine for config files with non-ASCII content.
+    Utf16Le,
+    /// UTF-16 big endian with a byte order mark.
+    Utf16Be,
+    /// UTF-16 little endian without a byte order mark.
+    Utf16LeNoBom,
+    /// UTF-16 big endian without a byte order mark.
+    Utf16BeNoBom,
+    /// Latin-1, assumed for text files that are not valid UTF-8.
+    Latin1,
+}
+
+/// The line ending of a text file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+/// The decoded content of a text file along with its original format.
+pub struct TextFile {
+    pub content: String,
+    pub encoding: Encoding,
+    pub line_ending: LineEnding,
+}
+
+/// Read a text file, detecting its encoding and line ending. The encoding of
+/// files with a known text extension is guessed if needed. Returns an error
+/// in case of I/O issues or if the content cannot be decoded.
+pub fn read_text(path: &Path) -> io::Result<TextFile> {
+    let bytes = std::fs::read(path)?;
+    let (content, encoding) = match is_text_file(path) {
+        true => decode_text(&bytes)?,
+        false => decode(&bytes)?,
+    };
+    let line_ending = detect_line_ending(&content);
+    Ok(TextFile {
+        content,
+        encoding,
+        line_ending,
+    })
+}
+
+/// Read a text file into a string like [`std::fs::read_to_string`], decoding
+/// any supported encoding.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    read_text(path.as_ref()).map(|file| file.content)
+}
+
+/// Write a text file in the given encoding. Line endings are written as they
+/// appear in the content.
+pub fn write_text(path: &Path, content: &str, encoding: Encoding) -> io::Result<()> {
+    std::fs::write(path, encode(content, encoding)?)
+}
+
+/// Decode bytes based on their byte order mark, or as UTF-8 without one.
+/// Returns an error if the bytes are not valid in the detected encoding.
+pub fn decode(bytes: &[u8]) -> io::Result<(String, Encoding)> {
+    let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidData, err);
+    if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        let content = String::from_utf8(rest.to_vec()).map_err(|err| invalid(err.to_string()))?;
+        return Ok((content, Encoding::Utf8Bom));
+    }
+    if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+        let units = to_units(rest, u16::from_le_bytes)?;
+        let content = String::from_utf16(&units).map_err(|err| invalid(err.to_string()))?;
+        return Ok((content, Encoding::Utf16Le));
+    }
+    if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+        let units = to_units(rest, u16::from_be_bytes)?;
+        let content = String::from_utf16(&units).map_err(|err| invalid(err.to_string()))?;
+        return Ok((content, Encoding::Utf16Be));
+    }
+    let content = String::from_utf8(bytes.to_vec()).map_err(|err| invalid(err.to_string()))?;
+    Ok((content, Encoding::Utf8))
+}
+
+/// Decode bytes that are known to be text like [`decode`], but detect UTF-16
+/// without a byte order mark from the position of zero bytes and fall back
+/// to Latin-1 for content that is not valid UTF-8.
+pub fn decode_text(bytes: &[u8]) -> io::Result<(String, Encoding)> {
+    let has_bom = [UTF8_BOM, UTF16LE_BOM, UTF16BE_BOM]
+        .iter()
+        .any(|bom| bytes.starts_with(bom));
+    if has_bom {
+        return decode(bytes);
+    }
+    if let Some(encoding) = detect_utf16_without_bom(bytes) {
+        let from_bytes = match encoding {
+            Encoding::Utf16LeNoBom => u16::from_le_bytes,
+            _ => u16::from_be_bytes,
+        };
+        let units = to_units(bytes, from_bytes)?;
+        if let Ok(content) = String::from_utf16(&units) {
+            return Ok((content, encoding));
+        }
+    }
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(content) => Ok((content, Encoding::Utf8)),
+        Err(_) => Ok((
+            bytes.iter().map(|&byte| byte as char).collect(),
+            Encoding::Latin1,
+        )),
+    }
+}
+
+/// Encode content in the given encoding, including its byte order mark.
+/// Returns an error if the content cannot be represented in Latin-1.
+pub fn encode(content: &str, encoding: Encoding) -> io::Result<Vec<u8>> {
+    Ok(match encoding {
+        Encoding::Utf8 => content.as_bytes().to_vec(),
+        Encoding::Utf8Bom => [UTF8_BOM, content.as_bytes()].concat(),
+        Encoding::Utf16Le => UTF16LE_BOM
+            .iter()
+            .copied()
+            .chain(content.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect(),
+        Encoding::Utf16Be => UTF16BE_BOM
+            .iter()
+            .copied()
+            .chain(content.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect(),
+        Encoding::Utf16LeNoBom => content.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+        Encoding::Utf16BeNoBom => content.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        Encoding::Latin1 => content
+            .chars()
+            .map(u8::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(|_| {
+                let error_message = "content cannot be represented in Latin-1";
+                io::Error::new(io::ErrorKind::InvalidData, error_message)
+            })?,
+    })
+}
+
+/// Detect the line ending of content based on its first line break. Content
+/// without line breaks is assumed to use LF.
+pub fn detect_line_ending(content: &str) -> LineEnding {
+    match content.find('\n') {
+        Some(index) if content[..index].ends_with('\r') => LineEnding::CrLf,
+        _ => LineEnding::Lf,
+    }
+}
+
+fn is_text_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            TEXT_EXTENSIONS
+                .iter()
+                .any(|text_ext| text_ext.eq_ignore_ascii_case(ext))
+        })
+}
+
+/// Detect UTF-16 without a byte order mark, which unlike UTF-8 text contains
+/// zero bytes, mostly in the high byte of each code unit.
+fn detect_utf16_without_bom(bytes: &[u8]) -> Option<Encoding> {
+    if !bytes.len().is_multiple_of(2) || !bytes.contains(&0) {
+        return None;
+    }
+    let zeros_at = |offset| {
+        bytes
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|&&byte| byte == 0)
+            .count()
+    };
+    match zeros_at(1).cmp(&zeros_at(0)) {
+        std::cmp::Ordering::Greater => Some(Encoding::Utf16LeNoBom),
+        std::cmp::Ordering::Less => Some(Encoding::Utf16BeNoBom),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+fn to_units(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> io::Result<Vec<u16>> {
+    if !bytes.len().is_multiple_of(2) {
+        let error_message = "UTF-16 content must have an even number of bytes";
+        return Err(io::Error::new(io::ErrorKind::InvalidData, error_message));
+    }
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect())
+}
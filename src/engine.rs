@@ -1,42 +1,111 @@
-use std::path::Path;
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use crate::{
-    changes::{Change, Revert},
+    backup::{forget_rename, record_rename},
+    changes::{rename_with, Change, Revert},
     presentation::log,
+    vcs::{backend, clear_read_only, VcsOptions, VersionControl},
 };
 
 pub struct Engine {
     history: Vec<(Change, Revert)>,
+    /// The backup directory as originally given, which moves along with any
+    /// renamed directory that contains it.
+    backup_dir: PathBuf,
+    /// Where paths have moved to as a result of applied renames.
+    path_map: PathMap,
+    /// The version control system to prepare changes with, if any.
+    vcs: Option<Rc<dyn VersionControl>>,
+    /// Whether to clear read-only flags on files before changing them.
+    clear_read_only: bool,
 }
 
 impl Engine {
     pub fn new() -> Self {
-        Self { history: vec![] }
+        Self {
+            history: vec![],
+            backup_dir: PathBuf::new(),
+            path_map: PathMap::default(),
+            vcs: None,
+            clear_read_only: false,
+        }
+    }
+
+    /// Prepare changes with the given version control system options, e.g.
+    /// by opening files for edit and recording renames.
+    pub fn with_vcs(mut self, options: &VcsOptions) -> Self {
+        self.vcs = options.vcs.map(|vcs| Rc::from(backend(vcs)));
+        self.clear_read_only = options.clear_read_only;
+        self
+    }
+
+    /// Resolve a path to its current location after the renames applied so
+    /// far.
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.path_map.resolve(path)
     }
 
     /// Execute a series of changes in sequential order and stores the
     /// applied changes in history with appropriate revert actions.
+    /// Paths in each change are resolved against the renames applied before
+    /// it, so changes may refer to paths as they were before the changeset.
     /// Upon error, it will halt execution and return the error.
     pub fn execute(
         &mut self,
         changeset: Vec<Change>,
         backup_dir: impl AsRef<Path>,
     ) -> Result<(), String> {
+        self.backup_dir = backup_dir.as_ref().to_owned();
         for change in changeset {
-            log::step("apply", &change);
-            self.execute_single(change, backup_dir.as_ref())?;
+            self.execute_single(change)?;
         }
         Ok(())
     }
 
-    fn execute_single(&mut self, change: Change, backup_dir: &Path) -> Result<(), String> {
-        match change.apply(backup_dir) {
-            Ok(revert) => {
-                self.history.push((change, revert));
-                Ok(())
+    fn execute_single(&mut self, mut change: Change) -> Result<(), String> {
+        change.map_paths(|path| self.path_map.resolve(path));
+        log::step("apply", &change);
+        let revert = self.apply(&change).map_err(|err| err.to_string())?;
+        let renamed = match &change {
+            Change::RenameFile(params) => Some((absolute(&params.from), absolute(&params.to))),
+            _ => None,
+        };
+        if let Some((from, to)) = &renamed {
+            self.path_map.push(from, to);
+        }
+        self.history.push((change, revert));
+        // Renames are recorded in the manifest of the backup set, so that the
+        // set can undo the whole operation later on.
+        if let Some((from, to)) = renamed {
+            record_rename(&from, &to, &self.path_map.resolve(&self.backup_dir))
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, change: &Change) -> io::Result<Revert> {
+        if let (Change::RenameFile(params), Some(vcs)) = (change, &self.vcs) {
+            let vcs = Rc::clone(vcs);
+            let from = params.from.clone();
+            let to = params.to.clone();
+            rename_with(&from, &to, |from, to| vcs.rename(from, to))?;
+            return Ok(Box::new(move |_: &Path| {
+                rename_with(&to, &from, |from, to| vcs.rename(from, to))
+            }));
+        }
+        if let Some(path) = change.edited_path() {
+            if let Some(vcs) = &self.vcs {
+                vcs.open_for_edit(path)?;
+            }
+            if self.clear_read_only {
+                clear_read_only(path)?;
             }
-            Err(err) => Err(err.to_string()),
         }
+        change.apply(&self.path_map.resolve(&self.backup_dir))
     }
 
     /// Revert entire history of actions.
@@ -44,8 +113,59 @@ impl Engine {
     pub fn revert(&mut self) -> Result<(), String> {
         while let Some((change, revert)) = self.history.pop() {
             log::step("revert", &change);
-            revert().map_err(|err| err.to_string())?;
+            revert(&self.path_map.resolve(&self.backup_dir)).map_err(|err| err.to_string())?;
+            if let Change::RenameFile(_) = change {
+                self.path_map.pop();
+                if let Err(err) = forget_rename(&self.path_map.resolve(&self.backup_dir)) {
+                    log::warning(format!("Could not update the backup manifest: {}", err));
+                }
+            }
         }
         Ok(())
     }
 }
+
+/// Tracks where files and directories have moved to as they are renamed, so
+/// that paths recorded before a rename can be resolved to their current
+/// location.
+#[derive(Default)]
+struct PathMap {
+    renames: Vec<(PathBuf, PathBuf)>,
+}
+
+impl PathMap {
+    fn push(&mut self, from: &Path, to: &Path) {
+        self.renames.push((absolute(from), absolute(to)));
+    }
+
+    fn pop(&mut self) {
+        self.renames.pop();
+    }
+
+    /// Resolve a path to its current location. Paths that have not moved are
+    /// returned as is.
+    fn resolve(&self, path: &Path) -> PathBuf {
+        let mut resolved = absolute(path);
+        let mut moved = false;
+        for (from, to) in &self.renames {
+            if let Ok(rest) = resolved.strip_prefix(from) {
+                resolved = match rest.as_os_str().is_empty() {
+                    true => to.clone(),
+                    false => to.join(rest),
+                };
+                moved = true;
+            }
+        }
+        match moved {
+            true => resolved,
+            false => path.to_owned(),
+        }
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    match env::current_dir() {
+        Ok(current_dir) if path.is_relative() => current_dir.join(path),
+        _ => path.to_owned(),
+    }
+}
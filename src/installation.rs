@@ -0,0 +1,254 @@
+//! Locating the engine that a project is built with, so that new names can
+//! be checked against the modules and plugins of the engine itself.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use ini::Ini;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::detection::detect_engine_association;
+
+/// Folders of the engine that never contain module or plugin descriptors.
+const SKIPPED_DIRS: &[&str] = &["Binaries", "Intermediate", "Content", "Resources"];
+
+/// Commonly used engine modules, which new names are checked against when
+/// the engine of a project cannot be located. A project module or plugin
+/// with the same name would shadow the engine module and fail to build. A
+/// located engine is checked against its own modules and plugins instead.
+const FALLBACK_ENGINE_NAMES: &[&str] = &[
+    "AIModule",
+    "AnimGraph",
+    "AnimGraphRuntime",
+    "ApplicationCore",
+    "AssetRegistry",
+    "AssetTools",
+    "AudioMixer",
+    "BlueprintGraph",
+    "Chaos",
+    "CinematicCamera",
+    "ClothingSystemRuntimeInterface",
+    "CommonUI",
+    "ContentBrowser",
+    "DeveloperSettings",
+    "EditorStyle",
+    "EditorSubsystem",
+    "EnhancedInput",
+    "Foliage",
+    "GameplayAbilities",
+    "GameplayTags",
+    "GameplayTasks",
+    "HTTP",
+    "ImageWrapper",
+    "InputCore",
+    "Json",
+    "JsonUtilities",
+    "Kismet",
+    "KismetCompiler",
+    "Landscape",
+    "LevelEditor",
+    "LevelSequence",
+    "MediaAssets",
+    "MovieScene",
+    "MovieSceneTracks",
+    "NavigationSystem",
+    "Networking",
+    "Niagara",
+    "OnlineSubsystem",
+    "OnlineSubsystemUtils",
+    "PhysicsCore",
+    "Projects",
+    "PropertyEditor",
+    "RHI",
+    "RenderCore",
+    "Renderer",
+    "Slate",
+    "SlateCore",
+    "Sockets",
+    "ToolMenus",
+    "UMG",
+    "UMGEditor",
+];
+
+/// Options for locating the engine of a project.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct EngineOptions {
+    /// The root of the engine, overriding the engine association of the
+    /// project.
+    pub engine_root: Option<PathBuf>,
+    /// A launcher installed list or `Install.ini` file that maps engine
+    /// associations to engine roots, overriding the defaults of the platform.
+    pub engine_registry: Option<PathBuf>,
+}
+
+/// Detect the names of the modules and plugins of the engine that a project
+/// is built with, given the path to either the project root directory or the
+/// project descriptor. Returns commonly used engine module names when the
+/// engine cannot be located.
+pub fn detect_engine_names(project: &Path, options: &EngineOptions) -> Result<Vec<String>, String> {
+    match locate_engine_root(project, options)? {
+        Some(engine_root) => Ok(detect_engine_module_and_plugin_names(&engine_root)),
+        None => Ok(FALLBACK_ENGINE_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect()),
+    }
+}
+
+/// Locate the root of the engine that a project is built with, either as
+/// given or by resolving the engine association of the project through the
+/// engine registry.
+pub fn locate_engine_root(
+    project: &Path,
+    options: &EngineOptions,
+) -> Result<Option<PathBuf>, String> {
+    if let Some(engine_root) = &options.engine_root {
+        return match engine_root.join("Engine").is_dir() {
+            true => Ok(Some(engine_root.clone())),
+            false => Err("engine root must contain an Engine folder".into()),
+        };
+    }
+    let association = match detect_engine_association(project)? {
+        Some(association) => association,
+        None => return Ok(None),
+    };
+    if let Some(registry) = &options.engine_registry {
+        return match resolve_association(&read_registry(registry)?, &association) {
+            Some(engine_root) => Ok(Some(engine_root)),
+            None => Err(format!(
+                "engine association {} is not listed in {}",
+                association,
+                registry.display()
+            )),
+        };
+    }
+    let installations = default_registries()
+        .iter()
+        .filter(|registry| registry.is_file())
+        .filter_map(|registry| read_registry(registry).ok())
+        .flatten()
+        .chain(query_windows_registry(&association))
+        .collect::<Vec<_>>();
+    Ok(resolve_association(&installations, &association))
+}
+
+/// Detect the names of all modules and plugins of an engine, sorted.
+pub fn detect_engine_module_and_plugin_names(engine_root: &Path) -> Vec<String> {
+    let mut names = WalkDir::new(engine_root.join("Engine"))
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_str()?;
+            file_name
+                .strip_suffix(".Build.cs")
+                .or_else(|| file_name.strip_suffix(".uplugin"))
+                .map(str::to_owned)
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Read the engine installations listed in a registry file, which is either
+/// a launcher installed list (JSON) or an `Install.ini` file with an
+/// `[Installations]` section.
+fn read_registry(path: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    if let Ok(launcher_installed) = serde_json::from_str::<Value>(&content) {
+        let installations = launcher_installed
+            .get("InstallationList")
+            .and_then(Value::as_array)
+            .map(|list| {
+                list.iter()
+                    .filter_map(|installation| {
+                        let app_name = installation.get("AppName")?.as_str()?;
+                        let version = app_name.strip_prefix("UE_")?;
+                        let location = installation.get("InstallLocation")?.as_str()?;
+                        Some((version.to_owned(), PathBuf::from(location)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        return Ok(installations);
+    }
+    let install_ini = Ini::load_from_str(&content).map_err(|err| err.to_string())?;
+    Ok(install_ini
+        .section(Some("Installations"))
+        .map(|section| {
+            section
+                .iter()
+                .map(|(key, value)| (key.to_owned(), PathBuf::from(value)))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn resolve_association(installations: &[(String, PathBuf)], association: &str) -> Option<PathBuf> {
+    installations
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(association))
+        .map(|(_, engine_root)| engine_root.clone())
+}
+
+/// The registry files in which the launcher and source builds record engine
+/// installations on this platform.
+fn default_registries() -> Vec<PathBuf> {
+    let var = |name| {
+        env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    if cfg!(windows) {
+        var("PROGRAMDATA")
+            .map(|dir| dir.join("Epic/UnrealEngineLauncher/LauncherInstalled.dat"))
+            .into_iter()
+            .collect()
+    } else if cfg!(target_os = "macos") {
+        var("HOME")
+            .map(|home| home.join("Library/Application Support/Epic"))
+            .map(|dir| {
+                vec![
+                    dir.join("UnrealEngineLauncher/LauncherInstalled.dat"),
+                    dir.join("UnrealEngine/Install.ini"),
+                ]
+            })
+            .unwrap_or_default()
+    } else {
+        var("HOME")
+            .map(|home| home.join(".config/Epic/UnrealEngine/Install.ini"))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Source builds on Windows are registered in the registry rather than in a
+/// file, keyed by their identifier.
+fn query_windows_registry(association: &str) -> Option<(String, PathBuf)> {
+    if !cfg!(windows) {
+        return None;
+    }
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Epic Games\Unreal Engine\Builds",
+            "/v",
+        ])
+        .arg(association)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let engine_root = stdout.lines().find_map(|line| {
+        let (_, value) = line.split_once("REG_SZ")?;
+        Some(PathBuf::from(value.trim()))
+    })?;
+    Some((association.to_owned(), engine_root))
+}
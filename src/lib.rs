@@ -1,7 +1,15 @@
+pub mod backup;
 pub mod changes;
 pub mod cli;
+pub mod config;
+pub mod detection;
+pub mod encoding;
 pub mod engine;
+pub mod installation;
+pub mod package;
 pub mod presentation;
 pub mod unreal;
+pub mod validation;
+pub mod vcs;
 pub mod wizard;
 pub mod workflows;
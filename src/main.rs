@@ -1,13 +1,21 @@
+use std::convert::TryInto;
+
 use clap::Parser;
 use lmk::init_crash_reporter;
 use renom::{
     cli::{
         Cli,
-        Command::{RenameModule, RenamePlugin, RenameProject, RenameTarget, Wizard},
+        Command::{
+            Backup, Clean, Doctor, Graph, Inspect, Refs, RenameContentFolder, RenameModule,
+            RenamePlugin, RenameProject, RenameTarget, Wizard,
+        },
     },
     presentation::log,
     wizard::start_interactive_dialogue,
-    workflows::{rename_module, rename_plugin, rename_project, rename_target},
+    workflows::{
+        backup, clean, doctor, graph, inspect, refs, rename_content_folder, rename_module,
+        rename_plugin, rename_project, rename_target,
+    },
 };
 
 fn main() {
@@ -18,14 +26,36 @@ fn main() {
         None => { /* noop, clap will handle top-level help and version */ }
         Some(command) => {
             if let Err(e) = match command {
-                RenameProject(params) => rename_project(params.into()),
-                RenamePlugin(params) => rename_plugin(params.into()),
-                RenameTarget(params) => rename_target(params.into()),
-                RenameModule(params) => rename_module(params.into()),
+                RenameProject(params) => params
+                    .resolve_missing_args()
+                    .and_then(TryInto::try_into)
+                    .and_then(rename_project),
+                RenamePlugin(params) => params
+                    .resolve_missing_args()
+                    .and_then(TryInto::try_into)
+                    .and_then(rename_plugin),
+                RenameTarget(params) => params
+                    .resolve_missing_args()
+                    .and_then(TryInto::try_into)
+                    .and_then(rename_target),
+                RenameModule(params) => params
+                    .resolve_missing_args()
+                    .and_then(TryInto::try_into)
+                    .and_then(rename_module),
+                RenameContentFolder(params) => params
+                    .resolve_missing_args()
+                    .and_then(TryInto::try_into)
+                    .and_then(rename_content_folder),
                 Wizard => {
                     start_interactive_dialogue();
                     Ok(())
                 }
+                Inspect(params) => params.try_into().and_then(inspect),
+                Graph(params) => params.try_into().and_then(graph),
+                Doctor(params) => params.try_into().and_then(doctor),
+                Refs(params) => params.try_into().and_then(refs),
+                Backup(params) => params.try_into().and_then(backup),
+                Clean(params) => params.try_into().and_then(clean),
             } {
                 log::error(e);
             }
@@ -0,0 +1,359 @@
+//! Minimal support for reading and patching the name table of Unreal Engine
+//! package files (`.uasset` and `.umap`).
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+use crate::presentation::log;
+
+/// The tag that every package file starts with.
+const PACKAGE_FILE_TAG: u32 = 0x9E2A83C1;
+
+/// The first file version that serializes name hashes after each name.
+const VER_UE4_NAME_HASHES_SERIALIZED: i32 = 504;
+
+/// An entry in the name table of a package.
+#[derive(Debug, PartialEq)]
+pub struct NameEntry {
+    /// The name.
+    pub name: String,
+    /// The offset of the serialized string, including its length prefix.
+    pub offset: usize,
+    /// Whether the name is serialized as UTF-16 rather than Latin-1.
+    pub wide: bool,
+    /// Whether the name is followed by its hashes.
+    pub has_hashes: bool,
+}
+
+/// Read the name table of a package. Returns an error if the data is not a
+/// package or uses an unsupported format.
+pub fn read_name_table(bytes: &[u8]) -> Result<Vec<NameEntry>, String> {
+    let mut reader = Reader { bytes, position: 0 };
+    if reader.read_u32()? != PACKAGE_FILE_TAG {
+        return Err("file is not an Unreal Engine package".into());
+    }
+
+    let legacy_version = reader.read_i32()?;
+    if !(-8..=-2).contains(&legacy_version) {
+        return Err(format!(
+            "unsupported package legacy version {}",
+            legacy_version
+        ));
+    }
+    if legacy_version != -4 {
+        reader.read_i32()?; // legacy UE3 version
+    }
+    let file_version = reader.read_i32()?;
+    if legacy_version <= -8 {
+        reader.read_i32()?; // UE5 file version
+    }
+    reader.read_i32()?; // licensee version
+    let custom_version_count = reader.read_i32()?;
+    for _ in 0..custom_version_count {
+        match legacy_version {
+            -2 => reader.skip(8)?,
+            -5..=-3 => {
+                reader.skip(20)?;
+                reader.read_string()?;
+            }
+            _ => reader.skip(20)?,
+        }
+    }
+    reader.read_i32()?; // total header size
+    reader.read_string()?; // folder name
+    reader.read_u32()?; // package flags
+    let name_count = reader.read_i32()?;
+    let name_offset = reader.read_i32()?;
+    if name_count < 0 || name_offset < 0 {
+        return Err("package name table is corrupt".into());
+    }
+
+    // Unversioned packages are always saved with the latest format.
+    let has_hashes = file_version == 0 || file_version >= VER_UE4_NAME_HASHES_SERIALIZED;
+    reader.position = name_offset as usize;
+    let mut entries = vec![];
+    for _ in 0..name_count {
+        let offset = reader.position;
+        let (name, wide) = reader.read_string()?;
+        if has_hashes {
+            reader.skip(4)?;
+        }
+        entries.push(NameEntry {
+            name,
+            offset,
+            wide,
+            has_hashes,
+        });
+    }
+    Ok(entries)
+}
+
+/// Replace names in the name table of a package in place. Only replacements
+/// that keep the serialized size of a name can be applied, since anything
+/// else would shift every offset in the package. Returns the names that were
+/// replaced and the names that could not be replaced.
+pub fn replace_names(
+    bytes: &mut [u8],
+    replace: impl Fn(&str) -> Option<String>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut replaced = vec![];
+    let mut skipped = vec![];
+    for entry in read_name_table(bytes)? {
+        let Some(new_name) = replace(&entry.name) else {
+            continue;
+        };
+        let fits = match entry.wide {
+            true => new_name.encode_utf16().count() == entry.name.encode_utf16().count(),
+            false => new_name.len() == entry.name.len() && new_name.is_ascii(),
+        };
+        if !fits {
+            skipped.push(entry.name);
+            continue;
+        }
+
+        let mut position = entry.offset + 4;
+        match entry.wide {
+            true => {
+                for unit in new_name.encode_utf16() {
+                    bytes[position..position + 2].copy_from_slice(&unit.to_le_bytes());
+                    position += 2;
+                }
+                position += 2;
+            }
+            false => {
+                bytes[position..position + new_name.len()].copy_from_slice(new_name.as_bytes());
+                position += new_name.len() + 1;
+            }
+        }
+        if entry.has_hashes {
+            bytes[position..position + 2].copy_from_slice(
+                &(non_case_preserving_hash(&new_name, entry.wide) as u16).to_le_bytes(),
+            );
+            bytes[position + 2..position + 4]
+                .copy_from_slice(&(case_preserving_hash(&new_name) as u16).to_le_bytes());
+        }
+        replaced.push(entry.name);
+    }
+    Ok((replaced, skipped))
+}
+
+/// A package file and the names in its name table that reference another
+/// package.
+#[derive(Debug, PartialEq)]
+pub struct PackageReferences {
+    /// The package file.
+    pub path: PathBuf,
+    /// The referencing names.
+    pub names: Vec<String>,
+}
+
+/// Validate that references to a name in packages can be rewritten in place,
+/// which requires the new name to have the same length as the old one.
+/// Rewrites that change the length of names would require the packages to be
+/// reserialized, which is not supported.
+pub fn validate_new_name_fits_assets(new_name: &str, old_name: &str) -> Result<(), String> {
+    match new_name.len() == old_name.len() {
+        true => Ok(()),
+        false => Err(
+            "new name must have the same length as the old name to rewrite assets in place".into(),
+        ),
+    }
+}
+
+/// Print the package files that reference a package along with the
+/// referencing names.
+pub fn print_package_references(package: &str, references: &[PackageReferences], rewrite: bool) {
+    if references.is_empty() {
+        log::basic(format!("No assets reference {}.", package));
+        return;
+    }
+
+    log::header(format!(
+        "Found {} asset(s) referencing {}",
+        references.len(),
+        package
+    ));
+    for references in references {
+        log::step("asset", references.path.display());
+        for name in &references.names {
+            log::basic(format!("\t{}", name));
+        }
+    }
+    if !rewrite {
+        log::basic("These assets rely on redirects until they are resaved in the editor.");
+    }
+}
+
+/// Find all package files in the given content directories whose name table
+/// references the given package, e.g. `/Script/Module` or `/Plugin`. Files
+/// that cannot be parsed, such as cooked packages, are skipped.
+pub fn find_package_references(
+    content_dirs: &[PathBuf],
+    package: &str,
+) -> Result<Vec<PackageReferences>, String> {
+    let mut references = vec![];
+    for path in find_packages(content_dirs) {
+        let bytes = fs::read(&path).map_err(|err| err.to_string())?;
+        let Ok(entries) = read_name_table(&bytes) else {
+            continue;
+        };
+        let names = entries
+            .into_iter()
+            .map(|entry| entry.name)
+            .filter(|name| name_references_package(name, package))
+            .collect::<Vec<_>>();
+        if !names.is_empty() {
+            references.push(PackageReferences { path, names });
+        }
+    }
+    Ok(references)
+}
+
+/// Find all package files in the given content directories.
+pub fn find_packages(content_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    content_dirs
+        .iter()
+        .flat_map(|content_dir| {
+            WalkDir::new(content_dir)
+                .into_iter()
+                .filter_map(Result::ok)
+                .map(|entry| entry.into_path())
+                .filter(|path| is_package(path))
+        })
+        .collect()
+}
+
+/// Whether a file is a package file, judging by its extension.
+pub fn is_package(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "uasset" || ext == "umap")
+}
+
+/// Whether a name refers to the given package or to an object within it,
+/// e.g. `/Script/Module`, `/Script/Module.Class` or `/Plugin/Asset`.
+pub fn name_references_package(name: &str, package: &str) -> bool {
+    match name.strip_prefix(package) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.') || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// The case-insensitive name hash, as computed by `FCrc::Strihash_DEPRECATED`,
+/// which hashes one byte per character for Latin-1 names and two bytes per
+/// character for UTF-16 names.
+fn non_case_preserving_hash(name: &str, wide: bool) -> u32 {
+    let table = crc_table_deprecated();
+    let mut hash = 0u32;
+    for char in name.encode_utf16() {
+        let char = match char < 256 {
+            true => (char as u8).to_ascii_uppercase() as u16,
+            false => char,
+        };
+        let bytes = match wide {
+            true => vec![char as u8, (char >> 8) as u8],
+            false => vec![char as u8],
+        };
+        for byte in bytes {
+            hash = ((hash >> 8) & 0x00FFFFFF) ^ table[((hash ^ byte as u32) & 0xFF) as usize];
+        }
+    }
+    hash
+}
+
+/// The case-sensitive name hash, as computed by `FCrc::StrCrc32`, which
+/// treats every character as four bytes.
+fn case_preserving_hash(name: &str) -> u32 {
+    let table = crc_table();
+    let mut crc = !0u32;
+    for char in name.encode_utf16() {
+        for byte in (char as u32).to_le_bytes() {
+            crc = (crc >> 8) ^ table[((crc ^ byte as u32) & 0xFF) as usize];
+        }
+    }
+    !crc
+}
+
+/// The reflected CRC-32 table used by `FCrc::StrCrc32`.
+fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    for (index, entry) in table.iter_mut().enumerate() {
+        let mut crc = index as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xEDB88320,
+                _ => crc >> 1,
+            };
+        }
+        *entry = crc;
+    }
+    table
+}
+
+/// The non-reflected CRC-32 table used by `FCrc::Strihash_DEPRECATED`.
+fn crc_table_deprecated() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    for (index, entry) in table.iter_mut().enumerate() {
+        let mut crc = (index as u32) << 24;
+        for _ in 0..8 {
+            crc = match crc & 0x80000000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ 0x04C11DB7,
+            };
+        }
+        *entry = crc;
+    }
+    table
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.position + count;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or("unexpected end of package")?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), String> {
+        self.take(count).map(|_| ())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, String> {
+        self.read_u32().map(|value| value as i32)
+    }
+
+    /// Read a serialized string, returning the string and whether it was
+    /// serialized as UTF-16.
+    fn read_string(&mut self) -> Result<(String, bool), String> {
+        let length = self.read_i32()?;
+        if length >= 0 {
+            let bytes = self.take(length as usize)?;
+            let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+            Ok((bytes.iter().map(|&byte| byte as char).collect(), false))
+        } else {
+            let units = self
+                .take(length.unsigned_abs() as usize * 2)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>();
+            let units = units.strip_suffix(&[0]).unwrap_or(&units);
+            Ok((String::from_utf16_lossy(units), true))
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod prompt;
+
 pub mod log {
     use colored::*;
     use std::fmt::Display;
@@ -42,6 +44,11 @@ pub mod log {
         println!("\n\t[ Success ]\n\t{}\n", text.as_ref().bright_green());
     }
 
+    /// Print a warning.
+    pub fn warning<S: AsRef<str>>(text: S) {
+        println!("\n\t[ Warning ]\n\t{}\n", text.as_ref().yellow());
+    }
+
     /// Print an error.
     pub fn error<S: AsRef<str>>(text: S) {
         println!("\n\t[ Error ]\n\t{}\n", text.as_ref().red());
@@ -0,0 +1,272 @@
+//! Prompts shared by the interactive workflows, such as choosing a project,
+//! picking from a list and confirming planned changes.
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use inquire::{autocompletion::Replacement, Autocomplete, CustomUserError, Select, Text};
+
+use crate::{
+    changes::{group_by_file_type, preview, Change},
+    config::{config_path, Config},
+    detection::find_project_descriptors,
+};
+
+use super::log;
+
+/// How many path completions to suggest at once.
+const MAX_PATH_COMPLETIONS: usize = 20;
+
+/// Prompt for the path to a project, suggesting recently used projects and
+/// completing typed paths. The chosen project is remembered as the most
+/// recent one; failing to remember it does not fail the prompt.
+pub fn get_project_path_from_user(prompt: Text) -> Result<PathBuf, String> {
+    let config_path = config_path();
+    let mut config = config_path
+        .as_ref()
+        .ok()
+        .and_then(|config_path| Config::load(config_path).ok())
+        .unwrap_or_default();
+    let project = prompt
+        .with_autocomplete(ProjectPathCompleter {
+            recent_projects: config.existing_recent_projects(),
+        })
+        .with_help_message("tab to complete, ↑↓ to pick a recent project")
+        .prompt()
+        .map(PathBuf::from)
+        .map_err(|err| err.to_string())
+        .and_then(get_project_descriptor_from_user)?;
+    if let Ok(config_path) = &config_path {
+        let remembered = config
+            .add_recent_project(&project)
+            .and_then(|_| config.save(config_path));
+        if let Err(err) = remembered {
+            log::warning(format!("Could not remember the project: {}", err));
+        }
+    }
+    Ok(project)
+}
+
+/// Suggests recent projects that match the typed input, followed by the
+/// folders and project descriptors that complete the typed path.
+#[derive(Clone)]
+struct ProjectPathCompleter {
+    recent_projects: Vec<PathBuf>,
+}
+
+impl Autocomplete for ProjectPathCompleter {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        let mut suggestions = self
+            .recent_projects
+            .iter()
+            .map(|project| project.display().to_string())
+            .filter(|project| fuzzy_matches(input, project))
+            .collect::<Vec<_>>();
+        for completion in complete_path(input) {
+            if !suggestions.contains(&completion) {
+                suggestions.push(completion);
+            }
+        }
+        Ok(suggestions)
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted_suggestion: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        if highlighted_suggestion.is_some() {
+            return Ok(highlighted_suggestion);
+        }
+        let completions = complete_path(input);
+        let common_prefix = completions
+            .iter()
+            .skip(1)
+            .fold(completions.first().cloned(), |prefix, completion| {
+                prefix.map(|prefix| common_prefix(&prefix, completion).to_owned())
+            });
+        Ok(common_prefix.filter(|prefix| prefix.len() > input.len()))
+    }
+}
+
+/// Complete a typed path with the folders and project descriptors whose
+/// names start with the last component of the path, ignoring case. Folders
+/// are completed with a trailing separator so that completion can continue
+/// inside of them.
+pub fn complete_path(input: &str) -> Vec<String> {
+    if input.is_empty() {
+        return vec![];
+    }
+    let (typed_dir, prefix) = match input.rfind(['/', '\\']) {
+        Some(index) => input.split_at(index + 1),
+        None => ("", input),
+    };
+    let dir = match typed_dir {
+        "" => Path::new("."),
+        typed_dir => Path::new(typed_dir),
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let prefix = prefix.to_lowercase();
+    let mut completions = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_owned();
+            let is_dir = entry.path().is_dir();
+            let is_descriptor = entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "uproject");
+            let hidden = name.starts_with('.') && !prefix.starts_with('.');
+            match (is_dir || is_descriptor) && !hidden && name.to_lowercase().starts_with(&prefix) {
+                true => Some(format!(
+                    "{}{}{}",
+                    typed_dir,
+                    name,
+                    if is_dir { "/" } else { "" }
+                )),
+                false => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    completions.sort();
+    completions.truncate(MAX_PATH_COMPLETIONS);
+    completions
+}
+
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let length = a
+        .char_indices()
+        .zip(b.chars())
+        .take_while(|((_, a), b)| a == b)
+        .last()
+        .map_or(0, |((index, char), _)| index + char.len_utf8());
+    &a[..length]
+}
+
+/// Ask the user which descriptor to use when the given project root
+/// directory contains several, otherwise return the given path as is.
+pub fn get_project_descriptor_from_user(project: PathBuf) -> Result<PathBuf, String> {
+    if !project.is_dir() {
+        return Ok(project);
+    }
+    let descriptors = find_project_descriptors(&project)?;
+    if descriptors.len() < 2 {
+        return Ok(project);
+    }
+    let options = descriptors
+        .iter()
+        .filter_map(|descriptor| descriptor.file_name()?.to_str().map(str::to_owned))
+        .collect::<Vec<_>>();
+    Select::new("Choose a project descriptor:", options)
+        .prompt()
+        .map(|descriptor| project.join(descriptor))
+        .map_err(|err| err.to_string())
+}
+
+/// How many options a selection list shows at once.
+const SELECT_PAGE_SIZE: usize = 15;
+
+/// An option in a selection list, labelled with the name of the item and
+/// metadata that tells similar items apart.
+pub struct SelectOption<T> {
+    pub item: T,
+    pub label: String,
+}
+
+impl<T> Display for SelectOption<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Let the user pick an item from a list that can be narrowed down by typing
+/// parts of the label of an option.
+pub fn select_from_user<T>(message: &str, options: Vec<SelectOption<T>>) -> Result<T, String> {
+    Select::new(message, options)
+        .with_page_size(SELECT_PAGE_SIZE)
+        .with_filter(&|filter, _, label, _| fuzzy_matches(filter, label))
+        .with_help_message("type to filter, ↑↓ to move, enter to select")
+        .prompt()
+        .map(|option| option.item)
+        .map_err(|err| err.to_string())
+}
+
+/// Whether the characters of the filter appear in the text in order,
+/// ignoring case and whitespace in the filter, e.g. `onbd` matches
+/// `OnlineBuild`.
+pub fn fuzzy_matches(filter: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    filter
+        .chars()
+        .filter(|char| !char.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|wanted| text.any(|char| char == wanted))
+}
+
+/// What the user wants to do with the changeset of a workflow.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Confirmation {
+    /// Apply the changeset.
+    Apply,
+    /// Go back and choose a different new name.
+    EditNewName,
+    /// Leave the project as it is.
+    Cancel,
+}
+
+/// Show a summary of the planned changes grouped by file type and ask the
+/// user whether to apply them. The user can expand each group into the
+/// changes it contains before deciding.
+pub fn confirm_changeset_with_user(changeset: &[Change]) -> Result<Confirmation, String> {
+    let groups = group_by_file_type(changeset);
+    log::header(format!("Planned {} change(s)", changeset.len()));
+    for (file_type, changes) in &groups {
+        log::basic(format!("{:>5} {}", changes.len(), file_type));
+    }
+    loop {
+        let options = std::iter::once("Apply changes".to_owned())
+            .chain(
+                groups
+                    .iter()
+                    .map(|(file_type, _)| format!("Show {} changes", file_type)),
+            )
+            .chain(["Edit new name".to_owned(), "Cancel".to_owned()])
+            .collect::<Vec<_>>();
+        let last = options.len() - 1;
+        let selection = Select::new("Apply the planned changes?", options)
+            .raw_prompt()
+            .map_err(|err| err.to_string())?;
+        match selection.index {
+            0 => return Ok(Confirmation::Apply),
+            index if index == last => {
+                log::basic("Cancelled, the project was left unchanged.");
+                return Ok(Confirmation::Cancel);
+            }
+            index if index == last - 1 => return Ok(Confirmation::EditNewName),
+            index => print_changes(&groups[index - 1].1),
+        }
+    }
+}
+
+fn print_changes(changes: &[&Change]) {
+    for change in changes {
+        let mut lines = preview(change).into_iter();
+        if let Some(description) = lines.next() {
+            log::step("plan", description);
+        }
+        for line in lines {
+            match line.chars().next() {
+                Some('-') => log::basic(line.red().to_string()),
+                Some('+') => log::basic(line.green().to_string()),
+                _ => log::basic(line),
+            }
+        }
+    }
+}
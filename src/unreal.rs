@@ -1,4 +1,4 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 #[derive(Clone)]
 pub enum ModuleType {
@@ -25,6 +25,16 @@ impl Display for Module {
     }
 }
 
+/// A dependency of an Unreal Engine module on another module, as declared in
+/// the build file of the module.
+#[derive(Clone)]
+pub struct ModuleDependency {
+    /// The name of the module depended on.
+    pub name: String,
+    /// Whether the dependency is public or private.
+    pub public: bool,
+}
+
 /// Information about an Unreal Engine plugin.
 #[derive(Clone)]
 pub struct Plugin {
@@ -47,6 +57,8 @@ pub struct Target {
     pub name: String,
     // The path to the target file.
     pub path: PathBuf,
+    /// The type of the target, if declared.
+    pub r#type: Option<TargetType>,
 }
 
 impl Display for Target {
@@ -54,3 +66,40 @@ impl Display for Target {
         write!(f, "{}", &self.name)
     }
 }
+
+/// The type of an Unreal Engine target.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TargetType {
+    Game,
+    Editor,
+    Client,
+    Server,
+    Program,
+}
+
+impl Display for TargetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetType::Game => write!(f, "Game"),
+            TargetType::Editor => write!(f, "Editor"),
+            TargetType::Client => write!(f, "Client"),
+            TargetType::Server => write!(f, "Server"),
+            TargetType::Program => write!(f, "Program"),
+        }
+    }
+}
+
+impl FromStr for TargetType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Game" => Ok(TargetType::Game),
+            "Editor" => Ok(TargetType::Editor),
+            "Client" => Ok(TargetType::Client),
+            "Server" => Ok(TargetType::Server),
+            "Program" => Ok(TargetType::Program),
+            _ => Err(format!("unknown target type {}", s)),
+        }
+    }
+}
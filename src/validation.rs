@@ -0,0 +1,288 @@
+use inquire::{validator::Validation, CustomUserError};
+
+/// Names that Unreal Engine reserves for its own modules.
+const RESERVED_NAMES: &[&str] = &["Engine", "Core", "CoreUObject", "UnrealEd"];
+
+/// C++ keywords, which cannot be used as names since module, plugin and
+/// target names end up in generated identifiers.
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char8_t",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "concept",
+    "const",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "const_cast",
+    "continue",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+/// Kinds of things that can be renamed, which determine the rules that
+/// apply to a new name.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    Project,
+    Plugin,
+    Target,
+    Module,
+    ContentFolder,
+}
+
+impl NameKind {
+    fn noun(self) -> &'static str {
+        match self {
+            NameKind::Project => "project",
+            NameKind::Plugin => "plugin",
+            NameKind::Target => "target",
+            NameKind::Module => "module",
+            NameKind::ContentFolder => "content folder",
+        }
+    }
+
+    /// The longest name allowed. Projects are held to the limit of the
+    /// project browser in the editor.
+    fn max_len(self) -> usize {
+        match self {
+            NameKind::Project => 20,
+            _ => 30,
+        }
+    }
+
+    /// Whether the name ends up in C++ identifiers and module names, as
+    /// opposed to content paths.
+    fn is_code(self) -> bool {
+        self != NameKind::ContentFolder
+    }
+}
+
+/// Validate a new name for a project, plugin, target, module or content
+/// folder against the rules of Unreal Engine. The name must not collide
+/// with any of the existing names, ignoring case, other than the current
+/// name so that the case of a name can be changed. Modules and plugins are
+/// checked against the names of both, targets against the names of targets.
+pub fn validate_new_name(
+    old_name: &str,
+    new_name: &str,
+    kind: NameKind,
+    existing: &[&str],
+) -> Result<(), String> {
+    validate_name_is_not_empty(new_name)?;
+    validate_name_is_novel(old_name, new_name)?;
+    validate_name_is_concise(new_name, kind.max_len())?;
+    validate_name_is_ascii(new_name)?;
+    validate_name_starts_with_letter(new_name)?;
+    validate_name_is_valid_identifier(new_name)?;
+    if kind.is_code() {
+        validate_name_is_not_keyword(new_name)?;
+        validate_name_is_not_reserved(new_name)?;
+    }
+    let others = existing
+        .iter()
+        .copied()
+        .filter(|name| *name != old_name)
+        .collect::<Vec<_>>();
+    validate_name_is_unique(new_name, kind, &others)
+}
+
+/// Adapt the result of a validation to a prompt validation, capitalizing
+/// the error message for display.
+pub fn into_prompt_validation(result: Result<(), String>) -> Result<Validation, CustomUserError> {
+    match result {
+        Ok(()) => Ok(Validation::Valid),
+        Err(error_message) => {
+            let mut chars = error_message.chars();
+            let error_message = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => error_message,
+            };
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+pub fn validate_name_is_not_empty(name: &str) -> Result<(), String> {
+    match !name.trim().is_empty() {
+        true => Ok(()),
+        false => Err("new name must not be empty".into()),
+    }
+}
+
+pub fn validate_name_is_novel(old_name: &str, new_name: &str) -> Result<(), String> {
+    match old_name != new_name {
+        true => Ok(()),
+        false => Err("new name must be different than current name".into()),
+    }
+}
+
+pub fn validate_name_is_concise(name: &str, max_len: usize) -> Result<(), String> {
+    match name.chars().count() <= max_len {
+        true => Ok(()),
+        false => Err(format!(
+            "new name must not be longer than {} characters",
+            max_len
+        )),
+    }
+}
+
+pub fn validate_name_is_ascii(name: &str) -> Result<(), String> {
+    match name.is_ascii() {
+        true => Ok(()),
+        false => Err("new name must contain ASCII characters only".into()),
+    }
+}
+
+pub fn validate_name_starts_with_letter(name: &str) -> Result<(), String> {
+    match name.chars().next().is_some_and(|c| c.is_ascii_alphabetic()) {
+        true => Ok(()),
+        false => Err("new name must start with a letter".into()),
+    }
+}
+
+pub fn validate_name_is_valid_identifier(name: &str) -> Result<(), String> {
+    match name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        true => Ok(()),
+        false => {
+            let error_message =
+                "new name must be comprised of alphanumeric characters and underscores only";
+            Err(error_message.into())
+        }
+    }
+}
+
+pub fn validate_name_is_not_keyword(name: &str) -> Result<(), String> {
+    match CPP_KEYWORDS.contains(&name) {
+        false => Ok(()),
+        true => Err(format!("new name must not be the C++ keyword '{}'", name)),
+    }
+}
+
+pub fn validate_name_is_not_reserved(name: &str) -> Result<(), String> {
+    match find_ignoring_case(name, RESERVED_NAMES) {
+        None => Ok(()),
+        Some(reserved) => Err(format!(
+            "new name must not be '{}', which is reserved by Unreal Engine",
+            reserved
+        )),
+    }
+}
+
+pub fn validate_name_is_not_engine_name(name: &str, engine_names: &[String]) -> Result<(), String> {
+    match engine_names
+        .iter()
+        .find(|engine_name| engine_name.eq_ignore_ascii_case(name))
+    {
+        None => Ok(()),
+        Some(engine_name) => Err(format!(
+            "new name must not conflict with the engine module or plugin '{}'",
+            engine_name
+        )),
+    }
+}
+
+pub fn validate_name_is_unique(
+    name: &str,
+    kind: NameKind,
+    existing: &[&str],
+) -> Result<(), String> {
+    let noun = match kind {
+        NameKind::Module | NameKind::Plugin => "module or plugin",
+        kind => kind.noun(),
+    };
+    match find_ignoring_case(name, existing) {
+        None => Ok(()),
+        Some(other) => Err(format!(
+            "new name must not conflict with the existing {} '{}'",
+            noun, other
+        )),
+    }
+}
+
+fn find_ignoring_case<'a>(name: &str, names: &[&'a str]) -> Option<&'a str> {
+    names
+        .iter()
+        .find(|other| other.eq_ignore_ascii_case(name))
+        .copied()
+}
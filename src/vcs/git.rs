@@ -0,0 +1,86 @@
+use std::{fs, io, path::Path, process::Command};
+
+use crate::presentation::log;
+
+use super::{absolute, VersionControl};
+
+/// Git, through the git command line client.
+pub struct Git;
+
+impl VersionControl for Git {
+    fn uncommitted_changes(&self, project_root: &Path) -> Result<Vec<String>, String> {
+        let status = git(
+            project_root,
+            &["status", "--porcelain", "--", ".", ":(exclude).renom"],
+        )?;
+        Ok(status.lines().map(str::to_owned).collect())
+    }
+
+    fn open_for_edit(&self, _: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Rename a file or directory with `git mv` so that it is staged as a
+    /// rename. Untracked paths and the root of the repository, which git
+    /// cannot move, are renamed on the file system instead. Other failures
+    /// fall back to the file system with a warning, since the rename is then
+    /// not staged.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let from = absolute(from)?;
+        let to = absolute(to)?;
+        let dir = from.parent().unwrap_or(&from);
+        let from_arg = from.to_string_lossy();
+        let to_arg = to.to_string_lossy();
+        let error = match git(dir, &["mv", "--", &from_arg, &to_arg]) {
+            Ok(_) => return Ok(()),
+            Err(error) => error,
+        };
+        if !is_repository_root(&from) && is_tracked(&from) {
+            log::warning(format!(
+                "{}; renaming {} without staging it",
+                error,
+                from.display()
+            ));
+        }
+        fs::rename(&from, &to)
+    }
+
+    fn commit(&self, project_root: &Path, message: &str) -> Result<(), String> {
+        git(project_root, &["add", "--update", "--", "."])?;
+        git(project_root, &["commit", "--quiet", "--message", message])?;
+        Ok(())
+    }
+}
+
+/// Whether git tracks the path or, for directories, any file inside of it.
+fn is_tracked(path: &Path) -> bool {
+    let dir = path.parent().unwrap_or(path);
+    git(
+        dir,
+        &["ls-files", "--error-unmatch", "--", &path.to_string_lossy()],
+    )
+    .is_ok()
+}
+
+fn is_repository_root(path: &Path) -> bool {
+    match git(path, &["rev-parse", "--show-toplevel"]) {
+        Ok(root) => fs::canonicalize(root.trim()).ok() == fs::canonicalize(path).ok(),
+        Err(_) => false,
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|err| format!("failed to run git: {}", err))?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+        false => Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
@@ -0,0 +1,169 @@
+//! Integration with version control systems, so that renames are recorded as
+//! such, files are opened for edit before they are changed and changes do
+//! not get mixed up with uncommitted work.
+
+mod git;
+mod perforce;
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    changes::{AppendIniEntry, Change, ReplaceInAsset, ReplaceInFile, SetIniEntry},
+    presentation::log,
+};
+
+pub use self::{git::Git, perforce::Perforce};
+
+/// Version control systems that Renom can work with.
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum Vcs {
+    /// Git
+    Git,
+    /// Perforce, through the p4 command line client
+    #[value(name = "p4", alias = "perforce")]
+    Perforce,
+}
+
+/// Options for working with the version control system of a project.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct VcsOptions {
+    /// The version control system of the project, if any.
+    pub vcs: Option<Vcs>,
+    /// Whether to proceed with a warning instead of refusing to change a
+    /// working tree with uncommitted changes.
+    pub allow_dirty: bool,
+    /// Whether to commit the changes once they have been applied.
+    pub commit: bool,
+    /// Whether to clear read-only flags on files before changing them.
+    pub clear_read_only: bool,
+}
+
+/// Operations that a version control system performs on behalf of the
+/// engine and workflows.
+pub trait VersionControl {
+    /// List the uncommitted changes in a project. Renom's own files in
+    /// `.renom` are ignored.
+    fn uncommitted_changes(&self, project_root: &Path) -> Result<Vec<String>, String>;
+
+    /// Prepare a file to be changed, e.g. by opening it for edit.
+    fn open_for_edit(&self, path: &Path) -> io::Result<()>;
+
+    /// Rename a file or directory so that the rename is recorded.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    /// Commit the changes to files in a project.
+    fn commit(&self, project_root: &Path, message: &str) -> Result<(), String>;
+}
+
+/// Get the backend for a version control system.
+pub fn backend(vcs: Vcs) -> Box<dyn VersionControl> {
+    match vcs {
+        Vcs::Git => Box::new(Git),
+        Vcs::Perforce => Box::new(Perforce),
+    }
+}
+
+/// Check that a project is in a clean working tree before changing it.
+/// Uncommitted changes are only allowed if the changes are not going to be
+/// committed.
+pub fn check_working_tree(project_root: &Path, options: &VcsOptions) -> Result<(), String> {
+    let vcs = match options.vcs {
+        None if options.commit => return Err("cannot commit changes without a vcs".into()),
+        None => return Ok(()),
+        Some(vcs) => backend(vcs),
+    };
+    let changes = vcs.uncommitted_changes(project_root)?;
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let error_message = format!("project has uncommitted changes:\n{}", changes.join("\n"));
+    match options.allow_dirty && !options.commit {
+        true => {
+            log::warning(error_message);
+            Ok(())
+        }
+        false => Err(format!("{}\ncommit or stash them first", error_message)),
+    }
+}
+
+/// Generate a commit message that summarizes a changeset, with paths
+/// relative to the project root.
+pub fn commit_message(summary: &str, project_root: &Path, changeset: &[Change]) -> String {
+    let relative = |path: &Path| match path.strip_prefix(project_root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.display().to_string(),
+        _ => path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned(),
+    };
+    let mut lines: Vec<String> = vec![];
+    for change in changeset {
+        let line = match change {
+            Change::RenameFile(params) => {
+                format!(
+                    "Rename {} to {}",
+                    relative(&params.from),
+                    relative(&params.to)
+                )
+            }
+            Change::ReplaceInFile(ReplaceInFile { path, .. })
+            | Change::ReplaceInAsset(ReplaceInAsset { path, .. })
+            | Change::SetIniEntry(SetIniEntry { path, .. })
+            | Change::AppendIniEntry(AppendIniEntry { path, .. }) => {
+                format!("Update {}", relative(path))
+            }
+            Change::RemovePath(params) => format!("Remove {}", relative(&params.path)),
+        };
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    let mut chars = summary.chars();
+    let summary = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    format!("{}\n\n{}\n", summary, lines.join("\n"))
+}
+
+/// Commit the changes to files in a project, if requested.
+pub fn commit(project_root: &Path, options: &VcsOptions, message: &str) -> Result<(), String> {
+    match (options.vcs, options.commit) {
+        (Some(vcs), true) => {
+            backend(vcs).commit(project_root, message)?;
+            log::step("commit", message.lines().next().unwrap_or_default());
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Clear the read-only flag of a file, if set.
+pub fn clear_read_only(path: &Path) -> io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    if !permissions.readonly() {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+    match path.is_absolute() {
+        true => Ok(path.to_owned()),
+        false => Ok(env::current_dir()?.join(path)),
+    }
+}
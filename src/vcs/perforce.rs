@@ -0,0 +1,112 @@
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use super::{absolute, VersionControl};
+
+/// Perforce, through the p4 command line client. Files in a Perforce
+/// workspace are typically read-only until they are opened for edit.
+pub struct Perforce;
+
+impl VersionControl for Perforce {
+    fn uncommitted_changes(&self, project_root: &Path) -> Result<Vec<String>, String> {
+        let project_root = absolute(project_root).map_err(|err| err.to_string())?;
+        let files = files(&project_root);
+        let opened = p4(&project_root, &[OsStr::new("opened"), files.as_os_str()])
+            .map_err(|err| err.to_string())?;
+        Ok(opened.lines().map(str::to_owned).collect())
+    }
+
+    fn open_for_edit(&self, path: &Path) -> io::Result<()> {
+        let path = absolute(path)?;
+        p4(working_dir(&path), &[OsStr::new("edit"), path.as_os_str()]).map(|_| ())
+    }
+
+    /// Rename a file or directory with `p4 move` so that it is recorded as a
+    /// move. Paths that are not in the depot are renamed on the file system
+    /// instead.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let from = absolute(from)?;
+        let to = absolute(to)?;
+        let (from_files, to_files) = match from.is_dir() {
+            true => (files(&from), files(&to)),
+            false => (from.clone(), to.clone()),
+        };
+        let dir = working_dir(&from);
+        let moved = p4(dir, &[OsStr::new("edit"), from_files.as_os_str()])
+            .and_then(|_| {
+                let args = [
+                    OsStr::new("move"),
+                    from_files.as_os_str(),
+                    to_files.as_os_str(),
+                ];
+                p4(dir, &args)
+            })
+            .is_ok();
+        match (moved, from.exists()) {
+            (true, false) => Ok(()),
+            // Files that are not in the depot, such as build products, are
+            // left behind by `p4 move` and moved on the file system instead.
+            (true, true) => move_remaining(&from, &to),
+            (false, _) => fs::rename(&from, &to),
+        }
+    }
+
+    fn commit(&self, project_root: &Path, message: &str) -> Result<(), String> {
+        let project_root = absolute(project_root).map_err(|err| err.to_string())?;
+        let files = files(&project_root);
+        let args = [
+            OsStr::new("submit"),
+            OsStr::new("-d"),
+            OsStr::new(message),
+            files.as_os_str(),
+        ];
+        p4(&project_root, &args)
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// The Perforce file pattern for every file in a directory.
+fn files(dir: &Path) -> PathBuf {
+    dir.join("...")
+}
+
+/// Move whatever is left in a directory into another directory, merging
+/// subdirectories that exist in both.
+fn move_remaining(from: &Path, to: &Path) -> io::Result<()> {
+    if !to.exists() {
+        return fs::rename(from, to);
+    }
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        match entry.file_type()?.is_dir() {
+            true => move_remaining(&entry.path(), &target)?,
+            false => fs::rename(entry.path(), &target)?,
+        }
+    }
+    fs::remove_dir(from)
+}
+
+fn working_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(path)
+}
+
+fn p4(dir: &Path, args: &[&OsStr]) -> io::Result<String> {
+    let output = Command::new("p4").args(args).current_dir(dir).output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+        false => {
+            let error_message = format!(
+                "p4 {} failed: {}",
+                args[0].to_string_lossy(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Err(io::Error::other(error_message))
+        }
+    }
+}
@@ -3,8 +3,8 @@ use inquire::{Confirm, Select};
 use crate::{
     presentation::log,
     workflows::{
-        rename_module_interactive, rename_plugin_interactive, rename_project_interactive,
-        rename_target_interactive, Workflow,
+        rename_content_folder_interactive, rename_module_interactive, rename_plugin_interactive,
+        rename_project_interactive, rename_target_interactive, undo_interactive, Workflow,
     },
 };
 
@@ -32,6 +32,8 @@ pub fn start_interactive_dialogue() {
             Workflow::RenamePlugin => ok_or_quit!(rename_plugin_interactive()),
             Workflow::RenameTarget => ok_or_quit!(rename_target_interactive()),
             Workflow::RenameModule => ok_or_quit!(rename_module_interactive()),
+            Workflow::RenameContentFolder => ok_or_quit!(rename_content_folder_interactive()),
+            Workflow::Undo => ok_or_quit!(undo_interactive()),
         };
         if !user_wants_to_start_new_workflow() {
             break;
@@ -50,6 +52,8 @@ fn request_workflow_selection_from_user() -> Result<Workflow, String> {
         Workflow::RenamePlugin,
         Workflow::RenameTarget,
         Workflow::RenameModule,
+        Workflow::RenameContentFolder,
+        Workflow::Undo,
     ];
     Select::new("Choose a workflow:", options)
         .prompt()
@@ -0,0 +1,155 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    backup::{
+        backup_set_size, find_backup_root, find_backup_set, find_backup_sets, restore_backup_set,
+        Manifest,
+    },
+    detection::project_root_of,
+    presentation::log,
+};
+
+/// What to do with the backups of a project.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BackupAction {
+    /// List all backup sets.
+    List,
+    /// Remove backup sets older than the given age.
+    Prune { older_than: Duration },
+    /// Restore every file in the backup set with the given id.
+    Restore { id: String },
+}
+
+/// Params needed to manage the backups of an Unreal Engine project.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// Where the backups are stored, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// What to do with the backups.
+    pub action: BackupAction,
+}
+
+/// List, prune or restore the backups made by previous operations on an
+/// Unreal Engine project.
+pub fn backup(params: Params) -> Result<(), String> {
+    let project_root = project_root_of(&params.project_root);
+    if !project_root.is_dir() {
+        return Err("project root must be a directory".into());
+    }
+    let backup_root = match find_backup_root(&project_root, params.backup_dir.as_deref())? {
+        Some(backup_root) => backup_root,
+        None => match params.action {
+            BackupAction::Restore { id } => {
+                return Err(format!("backup set {} does not exist", id))
+            }
+            _ => {
+                log::basic("No backups found.");
+                return Ok(());
+            }
+        },
+    };
+    match params.action {
+        BackupAction::List => {
+            let manifests = find_backup_sets(&backup_root)?;
+            if manifests.is_empty() {
+                log::basic("No backups found.");
+                return Ok(());
+            }
+            log::header(format!("Found {} backup set(s)", manifests.len()));
+            for manifest in manifests {
+                log::step(
+                    &manifest.id,
+                    format!(
+                        "{}, {} file(s), {}, {} ago",
+                        manifest.operation,
+                        manifest.entries.len(),
+                        format_size(backup_set_size(&backup_root.join(&manifest.id))),
+                        format_age(Utc::now() - manifest.created_at)
+                    ),
+                );
+            }
+        }
+        BackupAction::Prune { older_than } => {
+            let cutoff = Utc::now() - older_than;
+            let mut pruned = 0;
+            for manifest in find_backup_sets(&backup_root)? {
+                if manifest.created_at >= cutoff {
+                    continue;
+                }
+                fs::remove_dir_all(backup_root.join(&manifest.id))
+                    .map_err(|err| err.to_string())?;
+                log::step("prune", format!("{} {}", manifest.id, manifest.operation));
+                pruned += 1;
+            }
+            log::success(format!("Pruned {} backup set(s).", pruned));
+        }
+        BackupAction::Restore { id } => {
+            let backup_dir = find_backup_set(&backup_root, &id)?;
+            let manifest = restore_backup_set(&backup_dir)?;
+            print_restored_entries(&manifest);
+            log::success(format!(
+                "Restored {} file(s) from backup set {}.",
+                manifest.entries.len(),
+                id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Print the entries of a restored backup set in the order they were
+/// restored in.
+pub fn print_restored_entries(manifest: &Manifest) {
+    for entry in manifest.entries.iter().rev() {
+        match &entry.renamed_to {
+            Some(renamed_to) => log::step(
+                "revert",
+                format!(
+                    "rename {} to {}",
+                    renamed_to.display(),
+                    entry.original.display()
+                ),
+            ),
+            None => log::step("restore", entry.original.display()),
+        }
+    }
+}
+
+/// Parse a positive age like `30d`, `12h` or `45m`.
+pub fn parse_age(age: &str) -> Result<Duration, String> {
+    let error_message = || format!("invalid age {}, expected e.g. 30d, 12h or 45m", age);
+    let (index, unit) = age.char_indices().last().ok_or_else(error_message)?;
+    let amount = age[..index]
+        .parse::<i64>()
+        .ok()
+        .filter(|&amount| amount > 0)
+        .ok_or_else(error_message)?;
+    match unit {
+        'w' => Ok(Duration::weeks(amount)),
+        'd' => Ok(Duration::days(amount)),
+        'h' => Ok(Duration::hours(amount)),
+        'm' => Ok(Duration::minutes(amount)),
+        _ => Err(error_message()),
+    }
+}
+
+/// Format an age like `3d`, `5h` or `12m`, rounding down to the largest
+/// unit.
+pub fn format_age(age: Duration) -> String {
+    match (age.num_days(), age.num_hours(), age.num_minutes()) {
+        (days, _, _) if days > 0 => format!("{}d", days),
+        (_, hours, _) if hours > 0 => format!("{}h", hours),
+        (_, _, minutes) => format!("{}m", minutes),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes >= 1 << 20 => format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64),
+        bytes if bytes >= 1 << 10 => format!("{:.1} KiB", bytes as f64 / (1 << 10) as f64),
+        bytes => format!("{} B", bytes),
+    }
+}
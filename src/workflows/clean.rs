@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    changes::{Change, RemovePath},
+    detection::{detect_project_descriptor, detect_project_plugins, project_root_of},
+    engine::Engine,
+    presentation::log,
+};
+
+/// Folders in the root of a project that only hold generated files.
+const PROJECT_GENERATED_DIRS: &[&str] = &[
+    "Binaries",
+    "Intermediate",
+    "Saved",
+    "DerivedDataCache",
+    ".vs",
+];
+
+/// Folders in the root of a plugin that only hold generated files.
+const PLUGIN_GENERATED_DIRS: &[&str] = &["Binaries", "Intermediate"];
+
+/// Params needed to clean the generated files of an Unreal Engine project.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+}
+
+/// Remove the generated files of an Unreal Engine project and all of its
+/// plugins, such as build products, caches and solution files. Generated
+/// files are deleted without backups, so no backup set is created.
+pub fn clean(params: Params) -> Result<(), String> {
+    let project_root = project_root_of(&params.project_root);
+    if !project_root.is_dir() {
+        return Err("project root must be a directory".into());
+    }
+    detect_project_descriptor(&params.project_root)?;
+    let plugin_roots = detect_project_plugins(&project_root)?
+        .into_iter()
+        .map(|plugin| plugin.root)
+        .collect::<Vec<_>>();
+    let changeset = plan_clean(&project_root, &plugin_roots);
+    if changeset.is_empty() {
+        log::basic("No generated files found.");
+        return Ok(());
+    }
+
+    let removed = changeset.len();
+    let mut engine = Engine::new();
+    if let Err(e) = engine.execute(changeset, PathBuf::new()) {
+        log::error(&e);
+        log::error("Failed to clean generated files.");
+        return Ok(());
+    }
+
+    log::success(format!(
+        "Removed {} generated file(s) and folder(s).",
+        removed
+    ));
+    Ok(())
+}
+
+/// Plan the removal of the generated files of a project and the given
+/// plugins. Only paths that exist are included.
+pub fn plan_clean(project_root: &Path, plugin_roots: &[PathBuf]) -> Vec<Change> {
+    let mut paths = PROJECT_GENERATED_DIRS
+        .iter()
+        .map(|dir| project_root.join(dir))
+        .collect::<Vec<_>>();
+    if let Ok(entries) = project_root.read_dir() {
+        let mut solutions = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sln"))
+            .collect::<Vec<_>>();
+        solutions.sort();
+        paths.extend(solutions);
+    }
+    for plugin_root in plugin_roots {
+        paths.extend(
+            PLUGIN_GENERATED_DIRS
+                .iter()
+                .map(|dir| plugin_root.join(dir)),
+        );
+    }
+    paths
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| Change::RemovePath(RemovePath::new(path)))
+        .collect()
+}
@@ -0,0 +1,232 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::{
+    changes::{Change, ReplaceInFile},
+    detection::{
+        detect_module_host_types, detect_project_config_files, export_macro,
+        find_implementing_source,
+    },
+    encoding::read_to_string,
+};
+
+use super::{Context, Issue};
+
+/// Check that the class in each module build file is named after the module.
+pub fn check_build_class_names(context: &Context) -> Vec<Issue> {
+    let class_regex =
+        Regex::new(r"class\s+(?P<class>\w+)\s*:\s*ModuleRules").expect("regex should be valid");
+    context
+        .modules
+        .iter()
+        .filter_map(|module| {
+            let build_file = module.root.join(&module.name).with_extension("Build.cs");
+            let content = read_to_string(&build_file).ok()?;
+            let class = class_regex.captures(&content)?.name("class")?.as_str();
+            (class != module.name).then(|| Issue {
+                description: format!(
+                    "build class {} does not match module {}",
+                    class, module.name
+                ),
+                fix: Some(Change::ReplaceInFile(ReplaceInFile::new(
+                    &build_file,
+                    format!(
+                        r"(?P<head>\bclass\s+|\bpublic\s+){}(?P<tail>\s*:\s*ModuleRules|\s*\()",
+                        class
+                    ),
+                    format!("${{head}}{}${{tail}}", module.name),
+                ))),
+            })
+        })
+        .collect()
+}
+
+/// Check that every module listed in the project descriptor and in plugin
+/// descriptors has a module folder.
+pub fn check_declared_modules_exist(context: &Context) -> Result<Vec<Issue>, String> {
+    let module_names = context
+        .modules
+        .iter()
+        .map(|module| module.name.as_str())
+        .collect::<HashSet<_>>();
+    let descriptors = std::iter::once(context.project_descriptor.clone()).chain(
+        context
+            .plugins
+            .iter()
+            .map(|plugin| plugin.root.join(&plugin.name).with_extension("uplugin")),
+    );
+
+    let mut issues = vec![];
+    for descriptor in descriptors {
+        let mut declared_modules = detect_module_host_types(&descriptor)?
+            .into_keys()
+            .collect::<Vec<_>>();
+        declared_modules.sort();
+        issues.extend(
+            declared_modules
+                .into_iter()
+                .filter(|declared| !module_names.contains(declared.as_str()))
+                .map(|declared| Issue {
+                    description: format!(
+                        "module {} is listed in descriptor {} but has no module folder",
+                        declared,
+                        descriptor.display()
+                    ),
+                    fix: None,
+                }),
+        );
+    }
+    Ok(issues)
+}
+
+/// Check that classes and structs in each module are exported with the
+/// export macro of that module.
+pub fn check_export_macros(context: &Context) -> Vec<Issue> {
+    let declaration_regex = Regex::new(r"\b(class|struct)\s+(?P<macro>[A-Z0-9_]+_API)\s")
+        .expect("regex should be valid");
+    context
+        .modules
+        .iter()
+        .flat_map(|module| {
+            let expected_macro = export_macro(&module.name);
+            source_files(&module.root)
+                .into_iter()
+                .filter_map(|file| {
+                    let content = read_to_string(&file).ok()?;
+                    let mut wrong_macros = declaration_regex
+                        .captures_iter(&content)
+                        .map(|captures| captures["macro"].to_owned())
+                        .filter(|r#macro| r#macro != &expected_macro)
+                        .collect::<Vec<_>>();
+                    wrong_macros.sort();
+                    wrong_macros.dedup();
+                    Some((file, wrong_macros))
+                })
+                .flat_map(|(file, wrong_macros)| {
+                    let expected_macro = expected_macro.clone();
+                    wrong_macros.into_iter().map(move |wrong_macro| Issue {
+                        description: format!(
+                            "export macro {} in file {} does not match module {}",
+                            wrong_macro,
+                            file.display(),
+                            module.name
+                        ),
+                        fix: Some(Change::ReplaceInFile(ReplaceInFile::new(
+                            &file,
+                            format!(r"\b{}\b", wrong_macro),
+                            &expected_macro,
+                        ))),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Check that the module implement macro of each module names the module.
+pub fn check_implement_macros(context: &Context) -> Vec<Issue> {
+    let implement_regex = Regex::new(
+        r"(?P<head>IMPLEMENT_(GAME_|PRIMARY_GAME_)?MODULE\s*\(\s*[^,]+?,\s*)(?P<name>\w+)",
+    )
+    .expect("regex should be valid");
+    context
+        .modules
+        .iter()
+        .filter_map(|module| {
+            let source = find_implementing_source(&module.root)?;
+            let content = read_to_string(&source).ok()?;
+            let name = implement_regex.captures(&content)?.name("name")?.as_str();
+            (name != module.name).then(|| Issue {
+                description: format!(
+                    "implement macro in file {} names module {} instead of {}",
+                    source.display(),
+                    name,
+                    module.name
+                ),
+                fix: Some(Change::ReplaceInFile(ReplaceInFile::new(
+                    &source,
+                    format!(
+                        r"(?P<head>IMPLEMENT_(GAME_|PRIMARY_GAME_)?MODULE\s*\(\s*[^,]+?,\s*){}\b",
+                        name
+                    ),
+                    format!("${{head}}{}", module.name),
+                ))),
+            })
+        })
+        .collect()
+}
+
+/// Check that package redirects point at modules and plugins that exist in
+/// the project.
+pub fn check_redirects(context: &Context) -> Result<Vec<Issue>, String> {
+    let redirect_regex = Regex::new(
+        r#"(?m)^\+PackageRedirects=\(.*NewName="/(?P<script>Script/)?(?P<package>\w+)/?".*\)\r?$"#,
+    )
+    .expect("regex should be valid");
+    let module_names = context
+        .modules
+        .iter()
+        .map(|module| module.name.as_str())
+        .collect::<HashSet<_>>();
+    let plugin_names = context
+        .plugins
+        .iter()
+        .map(|plugin| plugin.name.as_str())
+        .chain(["Game"])
+        .collect::<HashSet<_>>();
+
+    let mut issues = vec![];
+    for config_file in detect_project_config_files(&context.project_root)? {
+        let content = read_to_string(&config_file).map_err(|err| err.to_string())?;
+        for captures in redirect_regex.captures_iter(&content) {
+            let package = &captures["package"];
+            let is_script_package = captures.name("script").is_some();
+            let exists = match is_script_package {
+                true => module_names.contains(package),
+                false => plugin_names.contains(package),
+            };
+            if exists {
+                continue;
+            }
+            let redirect = captures.get(0).unwrap().as_str().trim_end();
+            issues.push(Issue {
+                description: format!(
+                    "redirect {} in config file {} points at nonexistent {} {}",
+                    redirect,
+                    config_file.display(),
+                    if is_script_package {
+                        "module"
+                    } else {
+                        "plugin"
+                    },
+                    package
+                ),
+                fix: Some(Change::ReplaceInFile(ReplaceInFile::new(
+                    &config_file,
+                    format!(r"(?m)^{}\r?\n?", regex::escape(redirect)),
+                    "",
+                ))),
+            });
+        }
+    }
+    Ok(issues)
+}
+
+fn source_files(module_root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(module_root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "h" || ext == "hpp" || ext == "cpp")
+        })
+        .collect()
+}
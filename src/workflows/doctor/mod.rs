@@ -0,0 +1,133 @@
+mod checks;
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{
+        detect_all_modules, detect_project_descriptor, detect_project_plugins, project_root_of,
+    },
+    engine::Engine,
+    presentation::log,
+    unreal::{Module, Plugin},
+};
+
+use self::checks::{
+    check_build_class_names, check_declared_modules_exist, check_export_macros,
+    check_implement_macros, check_redirects,
+};
+
+/// Params needed to check an Unreal Engine project for inconsistencies.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// Whether to apply fixes for the issues that can be fixed automatically.
+    pub fix: bool,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// Context needed to check an Unreal Engine project for inconsistencies.
+pub struct Context {
+    /// The root of the project.
+    pub project_root: PathBuf,
+    /// The descriptor of the project.
+    pub project_descriptor: PathBuf,
+    /// Code modules in the project.
+    pub modules: Vec<Module>,
+    /// Plugins in the project.
+    pub plugins: Vec<Plugin>,
+}
+
+/// An inconsistency in an Unreal Engine project, typically left behind by
+/// an incomplete manual rename.
+pub struct Issue {
+    /// A description of the issue.
+    pub description: String,
+    /// The change that fixes the issue, if it can be fixed automatically.
+    pub fix: Option<Change>,
+}
+
+/// Check an Unreal Engine project for inconsistencies and optionally fix them.
+pub fn doctor(params: Params) -> Result<(), String> {
+    validate_params(&params)?;
+    let context = gather_context(&params)?;
+    let issues = diagnose(&context)?;
+    print_issues(&issues);
+    if issues.is_empty() || !params.fix {
+        return Ok(());
+    }
+
+    let changeset = issues
+        .into_iter()
+        .filter_map(|issue| issue.fix)
+        .collect::<Vec<Change>>();
+    let backup_dir = create_backup_dir(&context.project_root, params.backup_dir.as_deref())?;
+    let mut engine = Engine::new();
+    if let Err(e) = engine.execute(changeset, backup_dir) {
+        log::error(&e);
+        engine.revert()?;
+        log::error("Failed to fix project issues.");
+        return Ok(());
+    }
+
+    log::success("Successfully fixed project issues.");
+    Ok(())
+}
+
+fn validate_params(params: &Params) -> Result<(), String> {
+    let project_root = project_root_of(&params.project_root);
+    if !project_root.is_dir() {
+        return Err("project root must be a directory".into());
+    }
+    detect_project_descriptor(&params.project_root)?;
+    match project_root.join("Source").is_dir() {
+        true => Ok(()),
+        false => Err("project root must contain a Source folder".into()),
+    }
+}
+
+/// Gather the context needed to check a project given the path to the
+/// project root directory or the project descriptor. Assumes that the Source
+/// folder exists.
+pub fn gather_context(params: &Params) -> Result<Context, String> {
+    let project_root = project_root_of(&params.project_root);
+    Ok(Context {
+        project_descriptor: detect_project_descriptor(&params.project_root)?,
+        modules: detect_all_modules(&project_root)?,
+        plugins: detect_project_plugins(&project_root)?,
+        project_root,
+    })
+}
+
+/// Run all consistency checks against a project.
+pub fn diagnose(context: &Context) -> Result<Vec<Issue>, String> {
+    let mut issues = vec![];
+    issues.extend(check_build_class_names(context));
+    issues.extend(check_declared_modules_exist(context)?);
+    issues.extend(check_export_macros(context));
+    issues.extend(check_implement_macros(context));
+    issues.extend(check_redirects(context)?);
+    Ok(issues)
+}
+
+fn print_issues(issues: &[Issue]) {
+    if issues.is_empty() {
+        log::success("No issues found.");
+        return;
+    }
+
+    log::header(format!("Found {} issue(s)", issues.len()));
+    for issue in issues {
+        log::step("issue", &issue.description);
+        match &issue.fix {
+            Some(fix) => log::step("fix", fix),
+            None => log::step("fix", "none available, fix manually"),
+        }
+    }
+}
+
+fn create_backup_dir(project_root: &Path, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    create_backup_set(&backup_root(project_root, backup_dir)?, "doctor --fix")
+}
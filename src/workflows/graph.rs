@@ -0,0 +1,340 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    detection::{
+        detect_all_modules, detect_module_dependencies, detect_plugin_dependencies,
+        detect_project_descriptor, detect_project_name, detect_project_plugins,
+        is_in_closest_plugin, project_root_of,
+    },
+    presentation::log,
+    unreal::{ModuleType, Plugin},
+};
+
+/// Output formats for the dependency graph.
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// JSON for consumption by other tools
+    Json,
+}
+
+/// Params needed to export the dependency graph of an Unreal Engine project.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// The format to export the graph in.
+    pub format: GraphFormat,
+}
+
+/// The kind of a node in the dependency graph.
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    /// A module in the project Source folder.
+    Project,
+    /// A module in a project plugin.
+    Plugin,
+    /// A module that is not part of the project, typically an engine module.
+    External,
+}
+
+/// A module in the dependency graph.
+#[derive(Serialize)]
+pub struct Node {
+    pub name: String,
+    pub kind: NodeKind,
+    pub plugin: Option<String>,
+}
+
+/// A dependency of one module or plugin on another.
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub public: bool,
+}
+
+/// The module and plugin dependency graph of a project.
+#[derive(Serialize)]
+pub struct DependencyGraph {
+    pub project: String,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub plugin_edges: Vec<Edge>,
+    /// Groups of modules that depend on each other in a cycle.
+    pub cycles: Vec<Vec<String>>,
+    /// Dependencies of plugin modules on project modules.
+    pub violations: Vec<Edge>,
+}
+
+/// Export the module and plugin dependency graph of an Unreal Engine project.
+pub fn graph(params: Params) -> Result<(), String> {
+    validate_params(&params)?;
+    let graph = build_graph(&params.project_root)?;
+    match params.format {
+        GraphFormat::Dot => log::basic(to_dot(&graph)),
+        GraphFormat::Json => {
+            let json = serde_json::to_string_pretty(&graph).map_err(|err| err.to_string())?;
+            log::basic(json);
+        }
+    }
+    Ok(())
+}
+
+fn validate_params(params: &Params) -> Result<(), String> {
+    let project_root = project_root_of(&params.project_root);
+    if !project_root.is_dir() {
+        return Err("project root must be a directory".into());
+    }
+    detect_project_descriptor(&params.project_root)?;
+    match project_root.join("Source").is_dir() {
+        true => Ok(()),
+        false => Err("project root must contain a Source folder".into()),
+    }
+}
+
+/// Build the dependency graph of a project given the path to the project root
+/// directory or the project descriptor. Assumes that the Source folder exists.
+pub fn build_graph(project: &Path) -> Result<DependencyGraph, String> {
+    let project_root = project_root_of(project);
+    let plugins = detect_project_plugins(&project_root)?;
+    let modules = detect_all_modules(&project_root)?
+        .into_iter()
+        .filter(|module| is_in_closest_plugin(module, &plugins))
+        .collect::<Vec<_>>();
+
+    let mut nodes = BTreeMap::new();
+    for module in &modules {
+        let kind = match module.r#type {
+            ModuleType::Project => NodeKind::Project,
+            ModuleType::Plugin => NodeKind::Plugin,
+        };
+        nodes.insert(
+            module.name.clone(),
+            Node {
+                name: module.name.clone(),
+                kind,
+                plugin: module.plugin.as_ref().map(|plugin| plugin.name.clone()),
+            },
+        );
+    }
+
+    let mut edges = vec![];
+    for module in &modules {
+        for dependency in detect_module_dependencies(module)? {
+            edges.push(Edge {
+                from: module.name.clone(),
+                to: dependency.name,
+                public: dependency.public,
+            });
+        }
+    }
+    for edge in &edges {
+        nodes.entry(edge.to.clone()).or_insert_with(|| Node {
+            name: edge.to.clone(),
+            kind: NodeKind::External,
+            plugin: None,
+        });
+    }
+
+    let violations = edges
+        .iter()
+        .filter(|edge| {
+            nodes[&edge.from].kind == NodeKind::Plugin && nodes[&edge.to].kind == NodeKind::Project
+        })
+        .cloned()
+        .collect();
+
+    Ok(DependencyGraph {
+        project: detect_project_name(project)?,
+        cycles: find_cycles(&edges),
+        nodes: nodes.into_values().collect(),
+        plugin_edges: gather_plugin_edges(&plugins)?,
+        edges,
+        violations,
+    })
+}
+
+fn gather_plugin_edges(plugins: &[Plugin]) -> Result<Vec<Edge>, String> {
+    let mut plugin_edges = vec![];
+    for plugin in plugins {
+        for dependency in detect_plugin_dependencies(plugin)? {
+            plugin_edges.push(Edge {
+                from: plugin.name.clone(),
+                to: dependency,
+                public: true,
+            });
+        }
+    }
+    Ok(plugin_edges)
+}
+
+/// Find all cycles in the graph by detecting strongly connected components
+/// with more than one node, or a single node that depends on itself.
+fn find_cycles(edges: &[Edge]) -> Vec<Vec<String>> {
+    let mut adjacency: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for edge in edges {
+        adjacency.entry(&edge.from).or_default().insert(&edge.to);
+        adjacency.entry(&edge.to).or_default();
+    }
+
+    let mut tarjan = Tarjan {
+        adjacency: &adjacency,
+        index: 0,
+        indices: HashMap::new(),
+        low_links: HashMap::new(),
+        stack: vec![],
+        on_stack: BTreeSet::new(),
+        components: vec![],
+    };
+    for node in adjacency.keys() {
+        if !tarjan.indices.contains_key(node) {
+            tarjan.visit(node);
+        }
+    }
+
+    tarjan
+        .components
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1 || adjacency[component[0].as_str()].contains(component[0].as_str())
+        })
+        .map(|mut component| {
+            component.sort();
+            component
+        })
+        .collect()
+}
+
+/// State for Tarjan's strongly connected components algorithm.
+struct Tarjan<'a> {
+    adjacency: &'a BTreeMap<&'a str, BTreeSet<&'a str>>,
+    index: usize,
+    indices: HashMap<&'a str, usize>,
+    low_links: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, node: &'a str) {
+        self.indices.insert(node, self.index);
+        self.low_links.insert(node, self.index);
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        for &next in &self.adjacency[node] {
+            if !self.indices.contains_key(next) {
+                self.visit(next);
+                let low_link = self.low_links[node].min(self.low_links[next]);
+                self.low_links.insert(node, low_link);
+            } else if self.on_stack.contains(next) {
+                let low_link = self.low_links[node].min(self.indices[next]);
+                self.low_links.insert(node, low_link);
+            }
+        }
+
+        if self.low_links[node] == self.indices[node] {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_owned());
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// Render the graph in Graphviz DOT format. Plugin modules are grouped in
+/// clusters, external modules are dashed, and dependencies that are part of a
+/// cycle or violate plugin boundaries are highlighted in red. Plugins are
+/// rendered as separate folder nodes with their own dependencies.
+pub fn to_dot(graph: &DependencyGraph) -> String {
+    let mut lines = vec![format!("digraph \"{}\" {{", graph.project)];
+    lines.push("    node [shape=box];".into());
+
+    let mut plugin_clusters: BTreeMap<&str, Vec<&Node>> = BTreeMap::new();
+    for node in &graph.nodes {
+        match (&node.kind, &node.plugin) {
+            (NodeKind::Plugin, Some(plugin)) => {
+                plugin_clusters.entry(plugin).or_default().push(node)
+            }
+            (NodeKind::External, _) => lines.push(format!("    \"{}\" [style=dashed];", node.name)),
+            _ => lines.push(format!("    \"{}\";", node.name)),
+        }
+    }
+    for (plugin, nodes) in plugin_clusters {
+        lines.push(format!("    subgraph \"cluster_{}\" {{", plugin));
+        lines.push(format!("        label=\"{}\";", plugin));
+        for node in nodes {
+            lines.push(format!("        \"{}\";", node.name));
+        }
+        lines.push("    }".into());
+    }
+
+    for edge in &graph.edges {
+        let mut attributes = vec![];
+        if !edge.public {
+            attributes.push("style=dotted");
+        }
+        let in_cycle = graph
+            .cycles
+            .iter()
+            .any(|cycle| cycle.contains(&edge.from) && cycle.contains(&edge.to));
+        if in_cycle || graph.violations.contains(edge) {
+            attributes.push("color=red");
+        }
+        match attributes.is_empty() {
+            true => lines.push(format!("    \"{}\" -> \"{}\";", edge.from, edge.to)),
+            false => lines.push(format!(
+                "    \"{}\" -> \"{}\" [{}];",
+                edge.from,
+                edge.to,
+                attributes.join(", ")
+            )),
+        }
+    }
+
+    let plugin_names = graph
+        .plugin_edges
+        .iter()
+        .flat_map(|edge| [&edge.from, &edge.to])
+        .collect::<BTreeSet<_>>();
+    for plugin in plugin_names {
+        lines.push(format!(
+            "    \"plugin:{}\" [label=\"{}\", shape=folder];",
+            plugin, plugin
+        ));
+    }
+    for edge in &graph.plugin_edges {
+        lines.push(format!(
+            "    \"plugin:{}\" -> \"plugin:{}\";",
+            edge.from, edge.to
+        ));
+    }
+
+    for cycle in &graph.cycles {
+        lines.push(format!("    // cycle: {}", cycle.join(", ")));
+    }
+    for violation in &graph.violations {
+        lines.push(format!(
+            "    // violation: plugin module {} depends on project module {}",
+            violation.from, violation.to
+        ));
+    }
+
+    lines.push("}".into());
+    lines.join("\n")
+}
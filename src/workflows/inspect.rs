@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    detection::{
+        detect_engine_association, detect_module_host_types, detect_plugin_modules,
+        detect_project_config_files, detect_project_descriptor, detect_project_modules,
+        detect_project_name, detect_project_plugins, detect_project_targets, export_macro,
+        find_headers_with_export_macro, is_in_closest_plugin, project_root_of,
+    },
+    presentation::log,
+    unreal::{Module, Plugin},
+};
+
+/// Output formats for the project inspection.
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum InspectFormat {
+    /// Human-readable text
+    Text,
+    /// JSON for consumption by other tools
+    Json,
+}
+
+/// Params needed to inspect an Unreal Engine project.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// The format to print the project structure in.
+    pub format: InspectFormat,
+}
+
+/// The project structure as detected by Renom.
+#[derive(Serialize)]
+pub struct ProjectReport {
+    pub name: String,
+    pub root: PathBuf,
+    pub engine_association: Option<String>,
+    pub targets: Vec<TargetReport>,
+    pub modules: Vec<ModuleReport>,
+    pub plugins: Vec<PluginReport>,
+    pub config_files: Vec<PathBuf>,
+    pub export_macros: Vec<ExportMacroReport>,
+}
+
+#[derive(Serialize)]
+pub struct TargetReport {
+    pub name: String,
+    pub r#type: Option<String>,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize)]
+pub struct ModuleReport {
+    pub name: String,
+    pub plugin: Option<String>,
+    pub host_type: Option<String>,
+    pub root: PathBuf,
+}
+
+#[derive(Serialize)]
+pub struct PluginReport {
+    pub name: String,
+    pub parent: Option<String>,
+    pub root: PathBuf,
+}
+
+#[derive(Serialize)]
+pub struct ExportMacroReport {
+    pub module: String,
+    pub r#macro: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// Print the structure of an Unreal Engine project as detected by Renom.
+pub fn inspect(params: Params) -> Result<(), String> {
+    validate_params(&params)?;
+    let report = gather_report(&params.project_root)?;
+    match params.format {
+        InspectFormat::Text => print_report(&report),
+        InspectFormat::Json => {
+            let json = serde_json::to_string_pretty(&report).map_err(|err| err.to_string())?;
+            log::basic(json);
+        }
+    }
+    Ok(())
+}
+
+fn validate_params(params: &Params) -> Result<(), String> {
+    match project_root_of(&params.project_root).is_dir() {
+        true => detect_project_descriptor(&params.project_root).map(|_| ()),
+        false => Err("project root must be a directory".into()),
+    }
+}
+
+/// Gather the structure of a project given the path to the project root
+/// directory or the project descriptor. Projects without a Source folder
+/// report no targets or modules.
+pub fn gather_report(project: &Path) -> Result<ProjectReport, String> {
+    let project_root = &project_root_of(project);
+    let has_source_dir = project_root.join("Source").is_dir();
+    let plugins = detect_project_plugins(project_root)?;
+    let modules = match has_source_dir {
+        true => detect_project_modules(project_root)?,
+        false => vec![],
+    }
+    .into_iter()
+    .chain(detect_plugin_modules(&plugins)?)
+    .filter(|module| is_in_closest_plugin(module, &plugins))
+    .collect::<Vec<Module>>();
+    let targets = match has_source_dir {
+        true => detect_project_targets(project_root)?,
+        false => vec![],
+    };
+    let host_types = detect_host_types(project, &plugins)?;
+
+    Ok(ProjectReport {
+        name: detect_project_name(project)?,
+        root: project_root.to_owned(),
+        engine_association: detect_engine_association(project)?,
+        targets: targets
+            .iter()
+            .map(|target| TargetReport {
+                name: target.name.clone(),
+                r#type: target.r#type.map(|r#type| r#type.to_string()),
+                path: relative_to(&target.path, project_root),
+            })
+            .collect(),
+        modules: modules
+            .iter()
+            .map(|module| ModuleReport {
+                name: module.name.clone(),
+                plugin: module.plugin.as_ref().map(|plugin| plugin.name.clone()),
+                host_type: host_types.get(&module.name).cloned(),
+                root: relative_to(&module.root, project_root),
+            })
+            .collect(),
+        plugins: plugins
+            .iter()
+            .map(|plugin| PluginReport {
+                name: plugin.name.clone(),
+                parent: find_parent_plugin(plugin, &plugins).map(|parent| parent.name.clone()),
+                root: relative_to(&plugin.root, project_root),
+            })
+            .collect(),
+        config_files: detect_project_config_files(project_root)?
+            .iter()
+            .map(|config_file| relative_to(config_file, project_root))
+            .collect(),
+        export_macros: modules
+            .iter()
+            .map(|module| ExportMacroReport {
+                module: module.name.clone(),
+                r#macro: export_macro(&module.name),
+                files: find_headers_with_export_macro(&module.root, &module.name)
+                    .iter()
+                    .map(|file| relative_to(file, project_root))
+                    .collect(),
+            })
+            .filter(|export_macro| !export_macro.files.is_empty())
+            .collect(),
+    })
+}
+
+/// Collect the host types of all modules declared in the project descriptor
+/// and in plugin descriptors.
+fn detect_host_types(
+    project: &Path,
+    plugins: &[Plugin],
+) -> Result<HashMap<String, String>, String> {
+    let mut host_types = detect_module_host_types(&detect_project_descriptor(project)?)?;
+    for plugin in plugins {
+        let descriptor = plugin.root.join(&plugin.name).with_extension("uplugin");
+        host_types.extend(detect_module_host_types(&descriptor)?);
+    }
+    Ok(host_types)
+}
+
+/// Find the closest plugin that contains the given plugin, if any.
+fn find_parent_plugin<'a>(plugin: &Plugin, plugins: &'a [Plugin]) -> Option<&'a Plugin> {
+    plugins
+        .iter()
+        .filter(|other| other.root != plugin.root && plugin.root.starts_with(&other.root))
+        .max_by_key(|other| other.root.components().count())
+}
+
+fn relative_to(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_owned()
+}
+
+fn print_report(report: &ProjectReport) {
+    log::header("Project");
+    log::basic(format!("Name: {}", report.name));
+    log::basic(format!("Root: {}", report.root.display()));
+    log::basic(format!(
+        "Engine association: {}",
+        report.engine_association.as_deref().unwrap_or("none")
+    ));
+
+    log::header("Targets");
+    for target in &report.targets {
+        log::basic(format!(
+            "{} ({}) {}",
+            target.name,
+            target.r#type.as_deref().unwrap_or("unknown type"),
+            target.path.display()
+        ));
+    }
+
+    log::header("Modules");
+    for module in &report.modules {
+        let host = match &module.plugin {
+            Some(plugin) => format!("plugin {}", plugin),
+            None => "project".to_owned(),
+        };
+        log::basic(format!(
+            "{} ({}, {}) {}",
+            module.name,
+            host,
+            module.host_type.as_deref().unwrap_or("undeclared"),
+            module.root.display()
+        ));
+    }
+
+    log::header("Plugins");
+    for plugin in &report.plugins {
+        match &plugin.parent {
+            Some(parent) => log::basic(format!(
+                "{} (nested in {}) {}",
+                plugin.name,
+                parent,
+                plugin.root.display()
+            )),
+            None => log::basic(format!("{} {}", plugin.name, plugin.root.display())),
+        }
+    }
+
+    log::header("Config files");
+    for config_file in &report.config_files {
+        log::basic(config_file.display().to_string());
+    }
+
+    log::header("Export macros");
+    for export_macro in &report.export_macros {
+        log::basic(format!(
+            "{} ({})",
+            export_macro.r#macro, export_macro.module
+        ));
+        for file in &export_macro.files {
+            log::basic(format!("  {}", file.display()));
+        }
+    }
+}
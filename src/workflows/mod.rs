@@ -1,11 +1,27 @@
+pub mod backup;
+pub mod clean;
+pub mod doctor;
+pub mod graph;
+pub mod inspect;
+pub mod refs;
+pub mod rename_content_folder;
 pub mod rename_module;
 pub mod rename_plugin;
 pub mod rename_project;
 pub mod rename_target;
+pub mod undo;
 mod workflow;
 
+pub use backup::*;
+pub use clean::*;
+pub use doctor::*;
+pub use graph::*;
+pub use inspect::*;
+pub use refs::*;
+pub use rename_content_folder::*;
 pub use rename_module::*;
 pub use rename_plugin::*;
 pub use rename_project::*;
 pub use rename_target::*;
+pub use undo::*;
 pub use workflow::*;
@@ -0,0 +1,405 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::{
+    changes::Change,
+    detection::{
+        detect_all_modules, detect_project_descriptor, detect_project_plugins,
+        detect_project_targets, project_root_of,
+    },
+    encoding::read_to_string,
+    package::{is_package, read_name_table},
+    presentation::log,
+    workflows::{
+        plan_module_rename, plan_plugin_rename, plan_target_rename, rename_module, rename_plugin,
+        rename_target,
+    },
+};
+
+/// The name to find references to.
+pub enum Subject {
+    Module(String),
+    Plugin(String),
+    Target(String),
+}
+
+impl Subject {
+    fn name(&self) -> &str {
+        match self {
+            Subject::Module(name) | Subject::Plugin(name) | Subject::Target(name) => name,
+        }
+    }
+}
+
+impl Display for Subject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subject::Module(name) => write!(f, "module {}", name),
+            Subject::Plugin(name) => write!(f, "plugin {}", name),
+            Subject::Target(name) => write!(f, "target {}", name),
+        }
+    }
+}
+
+/// Params needed to find references to an Unreal Engine module, plugin or
+/// target.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// The module, plugin or target to find references to.
+    pub subject: Subject,
+}
+
+/// The kind of an occurrence that a rename would not touch.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OccurrenceKind {
+    /// An occurrence in a line or block comment.
+    Comment,
+    /// An occurrence in a string literal.
+    StringLiteral,
+    /// An occurrence in code or config outside of comments and literals.
+    Code,
+    /// An occurrence in a file or directory name.
+    Path,
+    /// An occurrence in a binary file such as an asset.
+    Binary,
+}
+
+impl Display for OccurrenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OccurrenceKind::Comment => write!(f, "comment"),
+            OccurrenceKind::StringLiteral => write!(f, "string literal"),
+            OccurrenceKind::Code => write!(f, "code"),
+            OccurrenceKind::Path => write!(f, "path"),
+            OccurrenceKind::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+/// A location in the project that references a name.
+pub struct Reference {
+    /// The file that contains the reference.
+    pub path: PathBuf,
+    /// The line of the reference, for references in text files.
+    pub line: Option<usize>,
+    /// The text of the line or a description of the reference.
+    pub text: String,
+}
+
+/// All references to a name, split into those that a rename would touch and
+/// those that it would not.
+pub struct References {
+    pub touched: Vec<Reference>,
+    pub untouched: Vec<(Reference, OccurrenceKind)>,
+}
+
+/// Directories that are generated by the engine or by Renom and are not
+/// searched for references.
+const IGNORED_DIRS: &[&str] = &[
+    ".renom",
+    ".git",
+    ".vs",
+    "Binaries",
+    "Intermediate",
+    "Saved",
+    "DerivedDataCache",
+];
+
+/// Find all references to an Unreal Engine module, plugin or target.
+pub fn refs(params: Params) -> Result<(), String> {
+    validate_params(&params)?;
+    let references = find_references(&params)?;
+    print_references(&params, &references);
+    Ok(())
+}
+
+fn validate_params(params: &Params) -> Result<(), String> {
+    let project_root = &project_root_of(&params.project_root);
+    if !project_root.is_dir() {
+        return Err("project root must be a directory".into());
+    }
+    detect_project_descriptor(&params.project_root)?;
+    if !project_root.join("Source").is_dir() {
+        return Err("project root must contain a Source folder".into());
+    }
+    let exists = match &params.subject {
+        Subject::Module(name) => detect_all_modules(project_root)?
+            .iter()
+            .any(|module| &module.name == name),
+        Subject::Plugin(name) => detect_project_plugins(project_root)?
+            .iter()
+            .any(|plugin| &plugin.name == name),
+        Subject::Target(name) => detect_project_targets(project_root)?
+            .iter()
+            .any(|target| &target.name == name),
+    };
+    match exists {
+        true => Ok(()),
+        false => Err(format!("{} must be part of project", params.subject)),
+    }
+}
+
+/// Find all references to a module, plugin or target. The references that a
+/// rename would touch are derived from the rename changeset; all other
+/// occurrences of the name in the project are reported as untouched.
+pub fn find_references(params: &Params) -> Result<References, String> {
+    // The new name does not affect which references are touched, so the
+    // changeset is planned with the current name as a placeholder.
+    let changeset = plan_rename(params)?;
+    let name = params.subject.name();
+
+    let mut touched = vec![];
+    let mut touched_ranges: HashMap<PathBuf, Vec<Range<usize>>> = HashMap::new();
+    let mut touched_paths = vec![];
+    for change in &changeset {
+        match change {
+            Change::ReplaceInFile(params) => {
+                let Ok(content) = read_to_string(&params.path) else {
+                    continue;
+                };
+                for found in params.find_ranges(&content) {
+                    touched.push(Reference {
+                        path: params.path.clone(),
+                        line: Some(line_number(&content, found.start)),
+                        text: line_at(&content, found.start).trim().to_owned(),
+                    });
+                    touched_ranges
+                        .entry(params.path.clone())
+                        .or_default()
+                        .push(found);
+                }
+            }
+            Change::RenameFile(params) => {
+                touched.push(Reference {
+                    path: params.from.clone(),
+                    line: None,
+                    text: "renamed".into(),
+                });
+                touched_paths.push(params.from.clone());
+            }
+            Change::ReplaceInAsset(params) => touched.push(Reference {
+                path: params.path.clone(),
+                line: None,
+                text: format!("replace package {} in name table", params.from),
+            }),
+            Change::SetIniEntry(params) => touched.push(Reference {
+                path: params.path.clone(),
+                line: None,
+                text: format!("set [{}] {}", params.section, params.key),
+            }),
+            Change::AppendIniEntry(params) => touched.push(Reference {
+                path: params.path.clone(),
+                line: None,
+                text: format!("append {} to [{}]", params.key, params.section),
+            }),
+            // Generated files are not references to the subject.
+            Change::RemovePath(_) => continue,
+        }
+    }
+
+    let name_regex =
+        Regex::new(&format!(r"\b{}\b", regex::escape(name))).expect("regex should be valid");
+    let mut untouched = vec![];
+    for entry in WalkDir::new(project_root_of(&params.project_root))
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry.path()))
+        .filter_map(Result::ok)
+    {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy();
+        let is_project_root = entry.depth() == 0;
+        if !is_project_root
+            && name_regex.is_match(&file_name)
+            && !touched_paths.iter().any(|p| p == path)
+        {
+            untouched.push((
+                Reference {
+                    path: path.to_owned(),
+                    line: None,
+                    text: "name contains reference".into(),
+                },
+                OccurrenceKind::Path,
+            ));
+        }
+        if !path.is_file() {
+            continue;
+        }
+
+        if is_package(path) {
+            let occurrences = find_package_occurrences(path, &name_regex)?;
+            untouched.extend(
+                occurrences
+                    .into_iter()
+                    .map(|reference| (reference, OccurrenceKind::Binary)),
+            );
+            continue;
+        }
+        // Files that cannot be decoded are binary and hold no references.
+        let Ok(content) = read_to_string(path) else {
+            continue;
+        };
+        let ranges = touched_ranges.get(path).map(Vec::as_slice).unwrap_or(&[]);
+        for found in name_regex.find_iter(&content) {
+            if ranges.iter().any(|range| overlaps(range, &found.range())) {
+                continue;
+            }
+            untouched.push((
+                Reference {
+                    path: path.to_owned(),
+                    line: Some(line_number(&content, found.start())),
+                    text: line_at(&content, found.start()).trim().to_owned(),
+                },
+                classify(&content, found.start()),
+            ));
+        }
+    }
+
+    Ok(References { touched, untouched })
+}
+
+/// Find the names in the name table of a package that contain the name of
+/// the subject. Packages that cannot be parsed, such as cooked packages, are
+/// skipped.
+fn find_package_occurrences(path: &Path, name_regex: &Regex) -> Result<Vec<Reference>, String> {
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+    let Ok(entries) = read_name_table(&bytes) else {
+        return Ok(vec![]);
+    };
+    Ok(entries
+        .into_iter()
+        .filter(|entry| name_regex.is_match(&entry.name))
+        .map(|entry| Reference {
+            path: path.to_owned(),
+            line: None,
+            text: entry.name,
+        })
+        .collect())
+}
+
+fn plan_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let project_root = params.project_root.clone();
+    let name = params.subject.name().to_owned();
+    match &params.subject {
+        Subject::Module(_) => plan_module_rename(&rename_module::Params {
+            project_root,
+            module: name.clone(),
+            new_name: name,
+            scan_assets: false,
+            rewrite_assets: false,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine: Default::default(),
+        }),
+        Subject::Plugin(_) => plan_plugin_rename(&rename_plugin::Params {
+            project_root,
+            plugin: name.clone(),
+            new_name: name,
+            scan_assets: false,
+            rewrite_assets: false,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine: Default::default(),
+        }),
+        Subject::Target(_) => plan_target_rename(&rename_target::Params {
+            project_root,
+            target: name.clone(),
+            new_name: name,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine: Default::default(),
+        }),
+    }
+}
+
+fn is_ignored(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| IGNORED_DIRS.contains(&name))
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+fn line_number(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+fn line_at(content: &str, offset: usize) -> &str {
+    let start = content[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let end = content[offset..]
+        .find('\n')
+        .map_or(content.len(), |index| offset + index);
+    &content[start..end]
+}
+
+/// Classify an occurrence as being part of a comment, a string literal, or
+/// code. Recognizes C++ and C# comments as well as config file comments.
+fn classify(content: &str, offset: usize) -> OccurrenceKind {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let line_before = &before[line_start..];
+
+    let in_block_comment = match (before.rfind("/*"), before.rfind("*/")) {
+        (Some(open), Some(close)) => open > close,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    let in_line_comment = line_before.contains("//") || line_before.trim_start().starts_with(';');
+    if in_block_comment || in_line_comment {
+        return OccurrenceKind::Comment;
+    }
+    match line_before.matches('"').count() % 2 == 1 {
+        true => OccurrenceKind::StringLiteral,
+        false => OccurrenceKind::Code,
+    }
+}
+
+fn print_references(params: &Params, references: &References) {
+    log::header(format!(
+        "Renaming {} would touch {} reference(s)",
+        params.subject,
+        references.touched.len()
+    ));
+    for reference in &references.touched {
+        log::basic(format_reference(
+            &project_root_of(&params.project_root),
+            reference,
+        ));
+    }
+
+    log::header(format!(
+        "Renaming {} would not touch {} occurrence(s)",
+        params.subject,
+        references.untouched.len()
+    ));
+    for (reference, kind) in &references.untouched {
+        log::step(
+            kind,
+            format_reference(&project_root_of(&params.project_root), reference),
+        );
+    }
+}
+
+fn format_reference(project_root: &Path, reference: &Reference) -> String {
+    let path = reference
+        .path
+        .strip_prefix(project_root)
+        .unwrap_or(&reference.path);
+    match reference.line {
+        Some(line) => format!("{}:{}: {}", path.display(), line, reference.text),
+        None => format!("{}: {}", path.display(), reference.text),
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use crate::changes::{AppendIniEntry, Change, RenameFile, ReplaceInFile};
+
+use super::Context;
+
+/// Generate a changeset to rename a top-level Unreal Engine content folder.
+pub fn generate_changeset(context: &Context) -> Vec<Change> {
+    let Context {
+        project_root,
+        project_config_files,
+        folder_root,
+        folder: old_name,
+        new_name,
+    } = context;
+
+    let mut changeset = vec![];
+    changeset.push(rename_folder(folder_root, new_name));
+    changeset.extend(rename_folder_references_in_configs(
+        project_config_files,
+        old_name,
+        new_name,
+    ));
+    changeset.push(append_folder_redirect(project_root, old_name, new_name));
+
+    changeset
+}
+
+fn rename_folder(folder_root: &Path, new_name: &str) -> Change {
+    Change::RenameFile(RenameFile::new(
+        folder_root,
+        folder_root.with_file_name(new_name),
+    ))
+}
+
+/// Replace content paths under the folder in config files, including the new
+/// names of existing redirects to the folder.
+fn rename_folder_references_in_configs(
+    config_files: &[PathBuf],
+    old_name: &str,
+    new_name: &str,
+) -> Vec<Change> {
+    config_files
+        .iter()
+        .map(|config_file| {
+            Change::ReplaceInFile(ReplaceInFile::in_config(
+                config_file,
+                regex::escape(&format!("/Game/{old_name}/")),
+                format!("/Game/{new_name}/"),
+            ))
+        })
+        .collect()
+}
+
+fn append_folder_redirect(project_root: &Path, old_name: &str, new_name: &str) -> Change {
+    Change::AppendIniEntry(AppendIniEntry::new(
+        project_root.join("Config").join("DefaultEngine.ini"),
+        "CoreRedirects",
+        "+PackageRedirects",
+        format!(
+            r#"(OldName="/Game/{}/",NewName="/Game/{}/",MatchSubstring=true)"#,
+            old_name, new_name
+        ),
+    ))
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use inquire::{validator::Validation, CustomUserError, Text};
+
+use crate::{
+    detection::{detect_content_folders, find_project_descriptors, project_root_of},
+    presentation::prompt::{get_project_path_from_user, select_from_user, SelectOption},
+    validation::{into_prompt_validation, validate_new_name, NameKind},
+};
+
+use super::Params;
+
+impl Params {
+    /// Build params from whichever of the project root, content folder and
+    /// new name are given, soliciting the rest from the user.
+    pub fn from_user(
+        project_root: Option<PathBuf>,
+        folder: Option<String>,
+        new_name: Option<String>,
+    ) -> Result<Self, String> {
+        let project_root = match project_root {
+            Some(project_root) => project_root,
+            None => get_project_root_from_user()?,
+        };
+        let project_dir = project_root_of(&project_root);
+        let folder = match folder {
+            Some(folder) => folder,
+            None => get_target_folder_from_user(&detect_content_folders(&project_dir)?)?,
+        };
+        let new_name = match new_name {
+            Some(new_name) => new_name,
+            None => get_target_name_from_user(&folder, &detect_content_folders(&project_dir)?)?,
+        };
+        Ok(Self {
+            project_root,
+            folder,
+            new_name,
+            backup_dir: None,
+            vcs: Default::default(),
+        })
+    }
+}
+
+fn get_project_root_from_user() -> Result<PathBuf, String> {
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor)
+            .with_validator(validate_project_root_contains_content_dir),
+    )
+}
+
+fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
+    match project_root_of(Path::new(project_root)).is_dir() {
+        true => Ok(Validation::Valid),
+        false => {
+            let error_message = "Provided path is not a directory or a .uproject file";
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+fn validate_project_root_contains_project_descriptor(
+    project_root: &str,
+) -> Result<Validation, CustomUserError> {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
+            let error_message = "Provided directory does not contain a .uproject file";
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+fn validate_project_root_contains_content_dir(
+    project_root: &str,
+) -> Result<Validation, CustomUserError> {
+    match project_root_of(Path::new(project_root))
+        .join("Content")
+        .is_dir()
+    {
+        true => Ok(Validation::Valid),
+        false => {
+            let error_message = "Provided directory does not contain a Content folder";
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+fn get_target_folder_from_user(folders: &[String]) -> Result<String, String> {
+    let options = folders
+        .iter()
+        .map(|folder| SelectOption {
+            label: folder.clone(),
+            item: folder.clone(),
+        })
+        .collect();
+    select_from_user("Choose a content folder:", options)
+}
+
+fn get_target_name_from_user(current_name: &str, folders: &[String]) -> Result<String, String> {
+    let current_name = current_name.to_owned();
+    let names = folders.to_vec();
+    Text::new("Provide a new name for the content folder:")
+        .with_validator(move |input: &str| {
+            let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = validate_new_name(&current_name, input, NameKind::ContentFolder, &names);
+            into_prompt_validation(result)
+        })
+        .prompt()
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,178 @@
+mod changeset;
+mod interactive;
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{
+        detect_content_folders, detect_project_config_files, detect_project_descriptor,
+        project_root_of,
+    },
+    engine::Engine,
+    presentation::{
+        log,
+        prompt::{confirm_changeset_with_user, Confirmation},
+    },
+    validation::{validate_new_name, NameKind},
+    vcs::{check_working_tree, commit, commit_message, VcsOptions},
+};
+
+use self::changeset::generate_changeset;
+
+/// Params needed to rename a top-level Unreal Engine content folder.
+pub struct Params {
+    /// The root of the project, or the path to its project descriptor.
+    pub project_root: PathBuf,
+    /// The specific content folder to rename.
+    pub folder: String,
+    /// The new name for the content folder.
+    pub new_name: String,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// How to work with the version control system of the project.
+    pub vcs: VcsOptions,
+}
+
+/// Context needed to rename a top-level Unreal Engine content folder.
+pub struct Context {
+    /// The root of the project.
+    pub project_root: PathBuf,
+    /// Config files for the project.
+    pub project_config_files: Vec<PathBuf>,
+    /// The root of the specific content folder to rename.
+    pub folder_root: PathBuf,
+    /// The name of the specific content folder to rename.
+    pub folder: String,
+    /// The new name for the content folder.
+    pub new_name: String,
+}
+
+/// Rename a top-level Unreal Engine content folder interactively, soliciting
+/// input parameters from the user with validation and guided selection.
+pub fn rename_content_folder_interactive() -> Result<(), String> {
+    let mut params = Params::from_user(None, None, None)?;
+    loop {
+        match confirm_changeset_with_user(&plan_content_folder_rename(&params)?)? {
+            Confirmation::Apply => return rename_content_folder(params),
+            Confirmation::EditNewName => {
+                params = Params::from_user(Some(params.project_root), Some(params.folder), None)?
+            }
+            Confirmation::Cancel => return Ok(()),
+        }
+    }
+}
+
+/// Generate the changeset to rename a top-level Unreal Engine content folder
+/// without applying it. Assumes that the folder is part of the project.
+pub fn plan_content_folder_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let context = gather_context(params)?;
+    Ok(generate_changeset(&context))
+}
+
+/// Rename a top-level Unreal Engine content folder.
+pub fn rename_content_folder(params: Params) -> Result<(), String> {
+    validate_params(&params)?;
+    check_working_tree(&project_root_of(&params.project_root), &params.vcs)?;
+    let context = gather_context(&params)?;
+    let changeset = generate_changeset(&context);
+    let backup_dir = create_backup_dir(&context, params.backup_dir.as_deref())?;
+    let commit_message = commit_message(
+        &describe_operation(&context),
+        &context.project_root,
+        &changeset,
+    );
+    let mut engine = Engine::new().with_vcs(&params.vcs);
+    if let Err(e) = engine.execute(changeset, backup_dir) {
+        log::error(&e);
+        engine.revert()?;
+        print_failure_message(&context);
+        return Ok(());
+    }
+
+    commit(
+        &engine.resolve(&context.project_root),
+        &params.vcs,
+        &commit_message,
+    )?;
+    print_success_message(&context);
+    Ok(())
+}
+
+fn validate_params(params: &Params) -> Result<(), String> {
+    let project_root = project_root_of(&params.project_root);
+    validate_project_root_is_dir(&project_root)?;
+    detect_project_descriptor(&params.project_root)?;
+    validate_project_root_contains_content_dir(&project_root)?;
+    let folders = detect_content_folders(&project_root)?;
+    validate_folder_exists(&params.folder, &folders)?;
+    let names = folders.iter().map(String::as_str).collect::<Vec<_>>();
+    validate_new_name(
+        &params.folder,
+        &params.new_name,
+        NameKind::ContentFolder,
+        &names,
+    )?;
+    Ok(())
+}
+
+fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
+    match project_root.is_dir() {
+        true => Ok(()),
+        false => Err("project root must be a directory".into()),
+    }
+}
+
+fn validate_project_root_contains_content_dir(project_root: &Path) -> Result<(), String> {
+    match project_root.join("Content").is_dir() {
+        true => Ok(()),
+        false => Err("project root must contain a Content folder".into()),
+    }
+}
+
+fn validate_folder_exists(folder: &str, folders: &[String]) -> Result<(), String> {
+    match folders.iter().any(|other| other == folder) {
+        true => Ok(()),
+        false => Err("folder must be a top-level folder in the Content folder".into()),
+    }
+}
+
+fn gather_context(params: &Params) -> Result<Context, String> {
+    let project_root = project_root_of(&params.project_root);
+    Ok(Context {
+        project_config_files: detect_project_config_files(&project_root)?,
+        folder_root: project_root.join("Content").join(&params.folder),
+        project_root,
+        folder: params.folder.clone(),
+        new_name: params.new_name.clone(),
+    })
+}
+
+/// Describe the operation for backup sets and commit messages.
+fn describe_operation(context: &Context) -> String {
+    format!(
+        "rename content folder {} to {}",
+        context.folder, context.new_name
+    )
+}
+
+fn create_backup_dir(context: &Context, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let operation = describe_operation(context);
+    let backup_root = backup_root(&context.project_root, backup_dir)?;
+    create_backup_set(&backup_root, &operation)
+}
+
+fn print_success_message(context: &Context) {
+    log::success(format!(
+        "Successfully renamed content folder {} to {}.",
+        context.folder, context.new_name
+    ));
+}
+
+fn print_failure_message(context: &Context) {
+    log::error(format!(
+        "Failed to rename content folder {} to {}.",
+        context.folder, context.new_name
+    ));
+}
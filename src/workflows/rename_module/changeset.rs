@@ -1,12 +1,10 @@
-use std::{
-    fs,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use regex::Regex;
 
 use crate::{
-    changes::{AppendIniEntry, Change, RenameFile, ReplaceInFile},
+    changes::{AppendIniEntry, Change, RenameFile, ReplaceInAsset, ReplaceInFile},
+    encoding::read_to_string,
     unreal::{Module, ModuleType, Plugin},
 };
 
@@ -30,9 +28,16 @@ pub fn generate_changeset(context: &Context) -> Vec<Change> {
         new_name,
         source_with_implement_macro,
         headers_with_export_macro,
+        referencing_assets,
+        rewrite_assets,
     } = context;
 
     let mut changeset = vec![];
+    if *rewrite_assets {
+        changeset.extend(referencing_assets.iter().map(|references| {
+            replace_mod_reference_in_asset(&references.path, old_name, new_name)
+        }));
+    }
     changeset.push(rename_build_class(module_root, old_name, new_name));
     changeset.push(rename_build_file(module_root, old_name, new_name));
 
@@ -95,7 +100,7 @@ pub fn generate_changeset(context: &Context) -> Vec<Change> {
 }
 
 fn update_implement_macro(source_file: &PathBuf, new_name: &str) -> Change {
-    let content = fs::read_to_string(&source_file).unwrap();
+    let content = read_to_string(&source_file).unwrap();
     let regex =
         Regex::new(r#"(?P<macro>IMPLEMENT_(GAME_|PRIMARY_GAME_)?MODULE)\((?P<impl>.+?),"#).unwrap();
     let captures = regex.captures(&content).unwrap();
@@ -138,6 +143,14 @@ fn append_mod_redirect(project_root: &Path, old_name: &str, new_name: &str) -> C
     ))
 }
 
+fn replace_mod_reference_in_asset(asset: &Path, old_name: &str, new_name: &str) -> Change {
+    Change::ReplaceInAsset(ReplaceInAsset::new(
+        asset,
+        format!("/Script/{}", old_name),
+        format!("/Script/{}", new_name),
+    ))
+}
+
 fn replace_mod_reference_in_target(target: &Path, old_name: &str, new_name: &str) -> Change {
     Change::ReplaceInFile(ReplaceInFile::new(
         target,
@@ -209,10 +222,11 @@ fn replace_mod_reference_in_plugin_descriptor(
     ))
 }
 
+/// Replace references like `/Script/Old.Class` in a config file.
 fn replace_mod_references_in_config(config: &Path, old_name: &str, new_name: &str) -> Change {
-    Change::ReplaceInFile(ReplaceInFile::new(
+    Change::ReplaceInFile(ReplaceInFile::in_config(
         config,
-        format!(r#"/Script/{}\."#, old_name),
-        format!(r#"/Script/{}."#, new_name),
+        format!(r"/Script/{}\b", old_name),
+        format!("/Script/{}", new_name),
     ))
 }
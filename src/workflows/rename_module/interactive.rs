@@ -1,49 +1,77 @@
-use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
+use std::path::{Path, PathBuf};
+
+use inquire::{validator::Validation, CustomUserError, Text};
+
+use crate::{
+    detection::{
+        detect_all_modules, detect_module_and_plugin_names, find_project_descriptors,
+        project_root_of,
+    },
+    installation::{detect_engine_names, EngineOptions},
+    presentation::prompt::{get_project_path_from_user, select_from_user, SelectOption},
+    unreal::Module,
+    validation::{
+        into_prompt_validation, validate_name_is_not_engine_name, validate_new_name, NameKind,
+    },
 };
 
-use inquire::{validator::Validation, CustomUserError, Select, Text};
-use regex::Regex;
-use walkdir::WalkDir;
-
-use crate::unreal::{Module, ModuleType, Plugin};
-
 use super::Params;
 
-pub fn get_params_from_user() -> Result<Params, String> {
-    let project_root = get_project_root_from_user()?;
-    let project_plugins = detect_project_plugins(&project_root)?;
-    let modules = detect_project_modules(&project_root)?
-        .into_iter()
-        .chain(detect_plugin_modules(&project_plugins)?)
-        .collect::<Vec<Module>>();
-    let target_module = get_target_module_from_user(&modules)?;
-    let target_name = get_target_name_from_user(&modules)?;
-
-    Ok(Params {
-        project_root,
-        module: target_module.name,
-        new_name: target_name,
-    })
+impl Params {
+    /// Build params from whichever of the project root, module and new name
+    /// are given, soliciting the rest from the user. The engine options are
+    /// used to check new names against the names of the engine.
+    pub fn from_user(
+        project_root: Option<PathBuf>,
+        module: Option<String>,
+        new_name: Option<String>,
+        engine: EngineOptions,
+    ) -> Result<Self, String> {
+        let project_root = match project_root {
+            Some(project_root) => project_root,
+            None => get_project_root_from_user()?,
+        };
+        let project_dir = project_root_of(&project_root);
+        let module = match module {
+            Some(module) => module,
+            None => get_target_module_from_user(&detect_all_modules(&project_dir)?)?.name,
+        };
+        let new_name = match new_name {
+            Some(new_name) => new_name,
+            None => {
+                let names = detect_module_and_plugin_names(&project_dir)?;
+                let engine_names = detect_engine_names(&project_root, &engine)?;
+                get_target_name_from_user(&module, names, engine_names)?
+            }
+        };
+        Ok(Self {
+            project_root,
+            module,
+            new_name,
+            scan_assets: false,
+            rewrite_assets: false,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine,
+        })
+    }
 }
 
 fn get_project_root_from_user() -> Result<PathBuf, String> {
-    Text::new("Project root directory path:")
-        .with_validator(validate_project_root_is_dir)
-        .with_validator(validate_project_root_contains_project_descriptor)
-        .with_validator(validate_project_root_contains_source_dir)
-        .prompt()
-        .map(|project_root| PathBuf::from(project_root))
-        .map_err(|err| err.to_string())
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor)
+            .with_validator(validate_project_root_contains_source_dir),
+    )
 }
 
 fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).is_dir() {
+    match project_root_of(Path::new(project_root)).is_dir() {
         true => Ok(Validation::Valid),
         false => {
-            let error_message = "Provided path is not a directory";
+            let error_message = "Provided path is not a directory or a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
     }
@@ -52,13 +80,9 @@ fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, Custom
 fn validate_project_root_contains_project_descriptor(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match fs::read_dir(project_root)?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(Validation::Valid),
-        false => {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
             let error_message = "Provided directory does not contain a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
@@ -68,7 +92,10 @@ fn validate_project_root_contains_project_descriptor(
 fn validate_project_root_contains_source_dir(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).join("Source").is_dir() {
+    match project_root_of(Path::new(project_root))
+        .join("Source")
+        .is_dir()
+    {
         true => Ok(Validation::Valid),
         false => {
             let error_message = "Provided directory does not contain a Source folder";
@@ -77,153 +104,46 @@ fn validate_project_root_contains_source_dir(
     }
 }
 
-/// Detect all plugins in a project given the path to the project root
-/// directory. Detects top-level plugins and nested plugins. Returns an error in
-/// case of I/O issues.
-fn detect_project_plugins(project_root: &PathBuf) -> Result<Vec<Plugin>, String> {
-    let plugins_dir = project_root.join("Plugins");
-    Ok(WalkDir::new(plugins_dir)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map_or(false, |ext| ext == "uplugin")
-        })
-        .map(|entry| Plugin {
-            root: entry.path().parent().unwrap().to_owned(),
-            name: entry
-                .path()
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned(),
-        })
-        .collect())
-}
-
-/// Detect all project modules in a project given the path to the project root
-/// directory. Detects top-level modules and nested modules. Assumes that the
-/// Source folder exists. Returns an error in case of I/O issues.
-fn detect_project_modules(project_root: &PathBuf) -> Result<Vec<Module>, String> {
-    let source_dir = project_root.join("Source");
-    assert!(source_dir.is_dir());
-    Ok(WalkDir::new(source_dir)
+/// Modules are grouped by their host plugin, listing project modules first.
+fn get_target_module_from_user(modules: &[Module]) -> Result<Module, String> {
+    let mut modules = modules.to_vec();
+    modules.sort_by(|a, b| {
+        host_plugin(a)
+            .cmp(&host_plugin(b))
+            .then(a.name.cmp(&b.name))
+    });
+    let options = modules
         .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| entry.path().is_dir() && dir_contains_module_descriptor(entry.path()))
-        .map(|entry| Module {
-            root: entry.path().to_owned(),
-            name: get_dir_name(&entry.path()),
-            r#type: ModuleType::Project,
-            plugin: None,
-        })
-        .collect())
-}
-
-/// Detect all plugin modules in a project given the list of project plugins.
-/// Detects top-level modules and nested modules. Returns an error in case of
-/// I/O issues.
-fn detect_plugin_modules(project_plugins: &[Plugin]) -> Result<Vec<Module>, String> {
-    Ok(project_plugins
-        .iter()
-        .flat_map(|plugin| {
-            WalkDir::new(&plugin.root)
-                .into_iter()
-                .filter_map(Result::ok)
-                .filter(|entry| {
-                    entry.path().is_dir() && dir_contains_module_descriptor(entry.path())
-                })
-                .map(move |entry| Module {
-                    root: entry.path().to_owned(),
-                    name: get_dir_name(&entry.path()),
-                    r#type: ModuleType::Plugin,
-                    plugin: Some(plugin.clone()),
-                })
+        .map(|module| SelectOption {
+            label: match host_plugin(&module) {
+                Some(plugin) => format!("{}  [plugin {}]", module.name, plugin),
+                None => format!("{}  [project]", module.name),
+            },
+            item: module,
         })
-        .collect())
-}
-
-fn dir_contains_module_descriptor(dir: &Path) -> bool {
-    assert!(dir.is_dir());
-    let dir_name = dir.file_name().expect("directory name should exist");
-    dir.join(dir_name).with_extension("Build.cs").is_file()
+        .collect();
+    select_from_user("Choose a module:", options)
 }
 
-fn get_dir_name(dir: &Path) -> String {
-    dir.file_name()
-        .expect("directory name should exist")
-        .to_str()
-        .expect("name should be valid Unicode")
-        .to_string()
+fn host_plugin(module: &Module) -> Option<String> {
+    module.plugin.as_ref().map(|plugin| plugin.name.clone())
 }
 
-fn get_target_module_from_user(modules: &[Module]) -> Result<Module, String> {
-    Select::new("Choose a module:", modules.to_vec())
-        .prompt()
-        .map_err(|err| err.to_string())
-}
-
-fn get_target_name_from_user(modules: &[Module]) -> Result<String, String> {
-    let modules = modules.to_vec();
+fn get_target_name_from_user(
+    current_name: &str,
+    names: Vec<String>,
+    engine_names: Vec<String>,
+) -> Result<String, String> {
+    let current_name = current_name.to_owned();
     Text::new("Provide a new name for the module:")
-        .with_validator(validate_target_name_is_not_empty)
-        .with_validator(validate_target_name_is_concise)
-        .with_validator(move |input: &str| validate_target_name_is_unique(input, &modules))
-        .with_validator(validate_target_name_is_valid_identifier)
+        .with_validator(move |input: &str| {
+            let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = validate_new_name(&current_name, input, NameKind::Module, &names);
+            into_prompt_validation(result)
+        })
+        .with_validator(move |input: &str| {
+            into_prompt_validation(validate_name_is_not_engine_name(input, &engine_names))
+        })
         .prompt()
         .map_err(|err| err.to_string())
 }
-
-fn validate_target_name_is_not_empty(target_name: &str) -> Result<Validation, CustomUserError> {
-    match !target_name.trim().is_empty() {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not be empty";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_concise(target_name: &str) -> Result<Validation, CustomUserError> {
-    let target_name_max_len = 30;
-    match target_name.len() <= target_name_max_len {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = format!(
-                "Target name must not be longer than {} characters",
-                target_name_max_len
-            );
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_unique(
-    target_name: &str,
-    modules: &[Module],
-) -> Result<Validation, CustomUserError> {
-    match modules.iter().all(|module| module.name != target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not conflict with another module";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_valid_identifier(
-    target_name: &str,
-) -> Result<Validation, CustomUserError> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message =
-                "Target name must be comprised of alphanumeric characters and underscores only";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
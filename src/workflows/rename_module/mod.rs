@@ -1,31 +1,56 @@
 mod changeset;
 mod interactive;
 
-use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
-};
-
-use regex::Regex;
-use walkdir::WalkDir;
+use std::path::{Path, PathBuf};
 
 use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{
+        detect_all_modules, detect_content_dirs, detect_module_and_plugin_names,
+        detect_project_config_files, detect_project_descriptor, detect_project_name,
+        detect_project_targets, find_headers_with_export_macro, find_implementing_source,
+        project_root_of,
+    },
     engine::Engine,
-    presentation::log,
-    unreal::{Module, ModuleType, Plugin},
+    installation::{detect_engine_names, EngineOptions},
+    package::{
+        find_package_references, print_package_references, validate_new_name_fits_assets,
+        PackageReferences,
+    },
+    presentation::{
+        log,
+        prompt::{confirm_changeset_with_user, Confirmation},
+    },
+    unreal::Module,
+    validation::{validate_name_is_not_engine_name, validate_new_name, NameKind},
+    vcs::{check_working_tree, commit, commit_message, VcsOptions},
+    workflows::plan_clean,
 };
 
-use self::{changeset::generate_changeset, interactive::get_params_from_user};
+use self::changeset::generate_changeset;
 
 /// Params needed to rename an Unreal Engine module.
 pub struct Params {
-    /// The root of the project.
+    /// The root of the project, or the path to its project descriptor.
     pub project_root: PathBuf,
     /// The specific module to rename.
     pub module: String,
     /// The new name for the module.
     pub new_name: String,
+    /// Whether to report assets that reference the module.
+    pub scan_assets: bool,
+    /// Whether to rewrite references to the module in assets. Requires the
+    /// new name to have the same length as the old name.
+    pub rewrite_assets: bool,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// How to work with the version control system of the project.
+    pub vcs: VcsOptions,
+    /// How to locate the engine that the project is built with.
+    pub engine: EngineOptions,
+    /// Whether to remove generated files that the rename leaves stale.
+    pub clean: bool,
 }
 
 /// Context needed to rename an Unreal Engine module.
@@ -48,22 +73,66 @@ pub struct Context {
     pub source_with_implement_macro: Option<PathBuf>,
     /// Header files that include the module export macro.
     pub headers_with_export_macro: Vec<PathBuf>,
+    /// Assets that reference the module, if assets were scanned.
+    pub referencing_assets: Vec<PackageReferences>,
+    /// Whether to rewrite references to the module in assets.
+    pub rewrite_assets: bool,
 }
 
 /// Rename an Unreal Engine module interactively, soliciting input parameters
-/// from the user with validation and guided selection.
+/// from the user with validation and guided selection, and confirming the
+/// planned changes before applying them.
 pub fn rename_module_interactive() -> Result<(), String> {
-    let params = get_params_from_user()?;
-    rename_module(params)
+    let mut params = Params::from_user(None, None, None, EngineOptions::default())?;
+    loop {
+        match confirm_changeset_with_user(&plan_module_rename(&params)?)? {
+            Confirmation::Apply => return rename_module(params),
+            Confirmation::EditNewName => {
+                params = Params::from_user(
+                    Some(params.project_root),
+                    Some(params.module),
+                    None,
+                    params.engine,
+                )?
+            }
+            Confirmation::Cancel => return Ok(()),
+        }
+    }
+}
+
+/// Generate the changeset to rename an Unreal Engine module without applying
+/// it. Assumes that the module is part of the project.
+pub fn plan_module_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let context = gather_context(params)?;
+    Ok(generate_changeset(&context))
 }
 
 /// Rename an Unreal Engine module.
 pub fn rename_module(params: Params) -> Result<(), String> {
     validate_params(&params)?;
+    check_working_tree(&project_root_of(&params.project_root), &params.vcs)?;
     let context = gather_context(&params)?;
-    let changeset = generate_changeset(&context);
-    let backup_dir = create_backup_dir(&context.project_root)?;
-    let mut engine = Engine::new();
+    if params.scan_assets || params.rewrite_assets {
+        print_package_references(
+            &format!("/Script/{}", context.module.name),
+            &context.referencing_assets,
+            context.rewrite_assets,
+        );
+    }
+    let mut changeset = generate_changeset(&context);
+    if params.clean {
+        changeset.extend(plan_clean(
+            &context.project_root,
+            &affected_plugin_roots(&context),
+        ));
+    }
+    let backup_dir = create_backup_dir(&context, params.backup_dir.as_deref())?;
+    let commit_message = commit_message(
+        &describe_operation(&context),
+        &context.project_root,
+        &changeset,
+    );
+    let mut engine = Engine::new().with_vcs(&params.vcs);
     if let Err(e) = engine.execute(changeset, backup_dir) {
         log::error(&e);
         engine.revert()?;
@@ -71,24 +140,30 @@ pub fn rename_module(params: Params) -> Result<(), String> {
         return Ok(());
     }
 
+    commit(
+        &engine.resolve(&context.project_root),
+        &params.vcs,
+        &commit_message,
+    )?;
     print_success_message(&context);
     Ok(())
 }
 
 fn validate_params(params: &Params) -> Result<(), String> {
-    validate_project_root_is_dir(&params.project_root)?;
-    validate_project_root_contains_project_descriptor(&params.project_root)?;
-    validate_project_root_contains_source_dir(&params.project_root)?;
-    let project_plugins = detect_project_plugins(&params.project_root)?;
-    let modules = detect_project_modules(&params.project_root)?
-        .into_iter()
-        .chain(detect_plugin_modules(&project_plugins)?)
-        .collect::<Vec<Module>>();
+    let project_root = project_root_of(&params.project_root);
+    validate_project_root_is_dir(&project_root)?;
+    detect_project_descriptor(&params.project_root)?;
+    validate_project_root_contains_source_dir(&project_root)?;
+    let modules = detect_all_modules(&project_root)?;
     validate_module_exists(&params.module, &modules)?;
-    validate_new_name_is_not_empty(&params.new_name)?;
-    validate_new_name_is_concise(&params.new_name)?;
-    validate_new_name_is_unique(&params.new_name, &modules)?;
-    validate_new_name_is_valid_identifier(&params.new_name)?;
+    let names = detect_module_and_plugin_names(&project_root)?;
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    validate_new_name(&params.module, &params.new_name, NameKind::Module, &names)?;
+    let engine_names = detect_engine_names(&params.project_root, &params.engine)?;
+    validate_name_is_not_engine_name(&params.new_name, &engine_names)?;
+    if params.rewrite_assets {
+        validate_new_name_fits_assets(&params.new_name, &params.module)?;
+    }
     Ok(())
 }
 
@@ -99,18 +174,6 @@ fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
     }
 }
 
-fn validate_project_root_contains_project_descriptor(project_root: &Path) -> Result<(), String> {
-    match fs::read_dir(project_root)
-        .map_err(|e| e.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(()),
-        false => Err("project root must contain a project descriptor".into()),
-    }
-}
-
 fn validate_project_root_contains_source_dir(project_root: &Path) -> Result<(), String> {
     match project_root.join("Source").is_dir() {
         true => Ok(()),
@@ -125,213 +188,14 @@ fn validate_module_exists(module: &str, modules: &[Module]) -> Result<(), String
     }
 }
 
-fn validate_new_name_is_not_empty(new_name: &str) -> Result<(), String> {
-    match !new_name.trim().is_empty() {
-        true => Ok(()),
-        false => Err("new name must not be empty".into()),
-    }
-}
-
-fn validate_new_name_is_concise(new_name: &str) -> Result<(), String> {
-    let new_name_max_len = 30;
-    match new_name.len() <= new_name_max_len {
-        true => Ok(()),
-        false => {
-            let error_message = format!(
-                "new name must not be longer than {} characters",
-                new_name_max_len
-            );
-            Err(error_message)
-        }
-    }
-}
-
-fn validate_new_name_is_unique(new_name: &str, modules: &[Module]) -> Result<(), String> {
-    match modules.iter().all(|module| module.name != new_name) {
-        true => Ok(()),
-        false => {
-            let error_message = "new name must not conflict with another module";
-            Err(error_message.into())
-        }
-    }
-}
-
-fn validate_new_name_is_valid_identifier(new_name: &str) -> Result<(), String> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(new_name) {
-        true => Ok(()),
-        false => {
-            let error_message =
-                "new name must be comprised of alphanumeric characters and underscores only";
-            Err(error_message.into())
-        }
-    }
-}
-
-/// Detect the name of a project given the path to the project root directory.
-/// Assumes that the directory exists and that it contains a project descriptor.
-/// Returns an error in case of I/O issues.
-fn detect_project_name(project_root: &PathBuf) -> Result<String, String> {
-    assert!(project_root.is_dir());
-
-    let project_descriptor = fs::read_dir(project_root)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| path.extension().map_or(false, |ext| ext == "uproject"))
-        .next()
-        .expect("project descriptor should exist");
-
-    project_descriptor
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-        .map(|name| name.to_owned())
-        .ok_or("project name is not valid Unicode".into())
-}
-
-/// Detect all plugins in a project given the path to the project root
-/// directory. Detects top-level plugins and nested plugins. Returns an error in
-/// case of I/O issues.
-fn detect_project_plugins(project_root: &PathBuf) -> Result<Vec<Plugin>, String> {
-    let plugins_dir = project_root.join("Plugins");
-    Ok(WalkDir::new(plugins_dir)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map_or(false, |ext| ext == "uplugin")
-        })
-        .map(|entry| Plugin {
-            root: entry.path().parent().unwrap().to_owned(),
-            name: entry
-                .path()
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned(),
-        })
-        .collect())
-}
-
-/// Detect all project modules in a project given the path to the project root
-/// directory. Detects top-level modules and nested modules. Assumes that the
-/// Source folder exists. Returns an error in case of I/O issues.
-fn detect_project_modules(project_root: &PathBuf) -> Result<Vec<Module>, String> {
-    let source_dir = project_root.join("Source");
-    assert!(source_dir.is_dir());
-    Ok(WalkDir::new(source_dir)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| entry.path().is_dir() && dir_contains_module_descriptor(entry.path()))
-        .map(|entry| Module {
-            root: entry.path().to_owned(),
-            name: get_dir_name(&entry.path()),
-            r#type: ModuleType::Project,
-            plugin: None,
-        })
-        .collect())
-}
-
-/// Detect all plugin modules in a project given the list of project plugins.
-/// Detects top-level modules and nested modules. Returns an error in case of
-/// I/O issues.
-fn detect_plugin_modules(project_plugins: &[Plugin]) -> Result<Vec<Module>, String> {
-    Ok(project_plugins
-        .iter()
-        .flat_map(|plugin| {
-            WalkDir::new(&plugin.root)
-                .into_iter()
-                .filter_map(Result::ok)
-                .filter(|entry| {
-                    entry.path().is_dir() && dir_contains_module_descriptor(entry.path())
-                })
-                .map(move |entry| Module {
-                    root: entry.path().to_owned(),
-                    name: get_dir_name(&entry.path()),
-                    r#type: ModuleType::Plugin,
-                    plugin: Some(plugin.clone()),
-                })
-        })
-        .collect())
-}
-
-fn detect_project_targets(project_root: &Path) -> Result<Vec<PathBuf>, String> {
-    let source_dir = project_root.join("Source");
-    assert!(source_dir.is_dir());
-    Ok(fs::read_dir(source_dir)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .path()
-                .to_str()
-                .map(|str| str.ends_with(".Target.cs"))
-                .unwrap_or(false)
-        })
-        .map(|entry| entry.path().to_owned())
-        .collect())
-}
-
-fn dir_contains_module_descriptor(dir: &Path) -> bool {
-    assert!(dir.is_dir());
-    let dir_name = dir.file_name().expect("directory name should exist");
-    dir.join(dir_name).with_extension("Build.cs").is_file()
-}
-
-fn get_dir_name(dir: &Path) -> String {
-    dir.file_name()
-        .expect("directory name should exist")
-        .to_str()
-        .expect("name should be valid Unicode")
-        .to_string()
-}
-
-fn detect_project_config_files(project_root: &Path) -> Result<Vec<PathBuf>, String> {
-    let config_dir = project_root.join("Config");
-    Ok(WalkDir::new(config_dir)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "ini"))
-        .map(|entry| entry.path().to_owned())
-        .collect())
-}
-
-fn find_implementing_source(module_root: &Path) -> Option<PathBuf> {
-    WalkDir::new(module_root)
-        .into_iter()
-        .filter_map(Result::ok)
-        .map(|entry| entry.path().to_owned())
-        .filter(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "cpp"))
-        .find(|source| {
-            fs::read_to_string(source).map_or(false, |content| content.contains("_MODULE"))
-        })
-}
-
-fn find_headers_with_export_macro(module_root: &Path, module_name: &str) -> Vec<PathBuf> {
-    WalkDir::new(module_root)
-        .into_iter()
-        .filter_map(Result::ok)
-        .map(|entry| entry.path().to_owned())
-        .filter(|path| {
-            fs::read_to_string(path).map_or(false, |content| {
-                content.contains(&format!("{}_API", module_name.to_uppercase()))
-            })
-        })
-        .collect()
-}
-
 fn gather_context(params: &Params) -> Result<Context, String> {
-    let project_root = params.project_root.clone();
-    let project_name = detect_project_name(&project_root)?;
-    let project_plugins = detect_project_plugins(&project_root)?;
-    let modules = detect_project_modules(&project_root)?
+    let project_root = project_root_of(&params.project_root);
+    let project_name = detect_project_name(&params.project_root)?;
+    let modules = detect_all_modules(&project_root)?;
+    let project_targets = detect_project_targets(&project_root)?
         .into_iter()
-        .chain(detect_plugin_modules(&project_plugins)?)
-        .collect::<Vec<Module>>();
-    let project_targets = detect_project_targets(&project_root)?;
+        .map(|target| target.path)
+        .collect();
     let project_config_files = detect_project_config_files(&project_root)?;
     let target_module = modules
         .iter()
@@ -341,6 +205,13 @@ fn gather_context(params: &Params) -> Result<Context, String> {
     let implementing_source = find_implementing_source(&target_module.root);
     let headers_with_export_macro =
         find_headers_with_export_macro(&target_module.root, &target_module.name);
+    let referencing_assets = match params.scan_assets || params.rewrite_assets {
+        true => find_package_references(
+            &detect_content_dirs(&project_root)?,
+            &format!("/Script/{}", target_module.name),
+        )?,
+        false => vec![],
+    };
 
     Ok(Context {
         project_root,
@@ -352,13 +223,33 @@ fn gather_context(params: &Params) -> Result<Context, String> {
         new_name: params.new_name.clone(),
         source_with_implement_macro: implementing_source,
         headers_with_export_macro,
+        referencing_assets,
+        rewrite_assets: params.rewrite_assets,
     })
 }
 
-fn create_backup_dir(project_root: &Path) -> Result<PathBuf, String> {
-    let backup_dir = project_root.join(".renom/backup");
-    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
-    Ok(backup_dir)
+/// The roots of the plugins whose build products are stale after the rename.
+fn affected_plugin_roots(context: &Context) -> Vec<PathBuf> {
+    context
+        .module
+        .plugin
+        .iter()
+        .map(|plugin| plugin.root.clone())
+        .collect()
+}
+
+/// Describe the operation for backup sets and commit messages.
+fn describe_operation(context: &Context) -> String {
+    format!(
+        "rename module {} to {}",
+        context.module.name, context.new_name
+    )
+}
+
+fn create_backup_dir(context: &Context, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let operation = describe_operation(context);
+    let backup_root = backup_root(&context.project_root, backup_dir)?;
+    create_backup_set(&backup_root, &operation)
 }
 
 fn print_success_message(context: &Context) {
@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use crate::{
-    changes::{AppendIniEntry, Change, RenameFile, ReplaceInFile},
+    changes::{AppendIniEntry, Change, RenameFile, ReplaceInAsset, ReplaceInFile},
     unreal::Plugin,
 };
 
@@ -13,16 +13,34 @@ pub fn generate_changeset(context: &Context) -> Vec<Change> {
         project_root,
         project_name,
         project_plugins,
+        project_config_files,
         plugin: Plugin {
             name: old_name,
             root: plugin_root,
         },
         new_name,
+        referencing_assets,
+        rewrite_assets,
     } = context;
 
     let descriptor = plugin_root.join(old_name).with_extension("uplugin");
     let mut changeset = vec![];
 
+    // Assets are rewritten first since they may live inside the plugin root.
+    if *rewrite_assets {
+        changeset.extend(referencing_assets.iter().map(|references| {
+            rename_plugin_references_in_asset(&references.path, old_name, new_name)
+        }));
+    }
+
+    // Config files are updated before the rename since they may live inside
+    // the plugin root.
+    changeset.extend(
+        project_config_files
+            .iter()
+            .map(|config_file| rename_plugin_references_in_config(config_file, old_name, new_name)),
+    );
+
     changeset.push(rename_plugin_descriptor(&descriptor, new_name));
     changeset.push(rename_plugin_root(plugin_root, new_name));
     changeset.push(rename_plugin_reference_in_project_descriptor(
@@ -87,6 +105,23 @@ fn rename_plugin_references_in_plugin(plugin: &Plugin, old_name: &str, new_name:
     ))
 }
 
+fn rename_plugin_references_in_asset(asset: &Path, old_name: &str, new_name: &str) -> Change {
+    Change::ReplaceInAsset(ReplaceInAsset::new(
+        asset,
+        format!("/{old_name}"),
+        format!("/{new_name}"),
+    ))
+}
+
+/// Replace content paths like `/Old/Maps/Entry.Entry` in a config file.
+fn rename_plugin_references_in_config(config: &Path, old_name: &str, new_name: &str) -> Change {
+    Change::ReplaceInFile(ReplaceInFile::in_config(
+        config,
+        format!(r#"(?m)(?P<head>^|["'=(])/{old_name}/"#),
+        format!("${{head}}/{new_name}/"),
+    ))
+}
+
 fn update_existing_redirects(project_root: &Path, old_name: &str, new_name: &str) -> Change {
     Change::ReplaceInFile(ReplaceInFile::new(
         project_root.join("Config").join("DefaultEngine.ini"),
@@ -1,41 +1,80 @@
-use std::{ffi::OsStr, fs, path::PathBuf};
-
-use inquire::{validator::Validation, CustomUserError, Select, Text};
-use regex::Regex;
-use walkdir::WalkDir;
-
-use crate::unreal::Plugin;
+use std::path::{Path, PathBuf};
+
+use inquire::{validator::Validation, CustomUserError, Text};
+
+use crate::{
+    detection::{
+        detect_module_and_plugin_names, detect_project_plugins, find_project_descriptors,
+        project_root_of,
+    },
+    installation::{detect_engine_names, EngineOptions},
+    presentation::prompt::{get_project_path_from_user, select_from_user, SelectOption},
+    unreal::Plugin,
+    validation::{
+        into_prompt_validation, validate_name_is_not_engine_name, validate_new_name, NameKind,
+    },
+};
 
 use super::Params;
 
-pub fn get_params_from_user() -> Result<Params, String> {
-    let project_root = get_project_root_from_user()?;
-    let project_plugins = detect_project_plugins(&project_root)?;
-    let target_plugin = get_target_plugin_from_user(&project_plugins)?;
-    let target_name = get_target_name_from_user(&project_plugins)?;
-
-    Ok(Params {
-        project_root,
-        plugin: target_plugin.name,
-        new_name: target_name,
-    })
+impl Params {
+    /// Build params from whichever of the project root, plugin and new name
+    /// are given, soliciting the rest from the user. The engine options are
+    /// used to check new names against the names of the engine.
+    pub fn from_user(
+        project_root: Option<PathBuf>,
+        plugin: Option<String>,
+        new_name: Option<String>,
+        engine: EngineOptions,
+    ) -> Result<Self, String> {
+        let project_root = match project_root {
+            Some(project_root) => project_root,
+            None => get_project_root_from_user()?,
+        };
+        let project_dir = project_root_of(&project_root);
+        let plugin = match plugin {
+            Some(plugin) => plugin,
+            None => {
+                get_target_plugin_from_user(&project_dir, &detect_project_plugins(&project_dir)?)?
+                    .name
+            }
+        };
+        let new_name = match new_name {
+            Some(new_name) => new_name,
+            None => {
+                let names = detect_module_and_plugin_names(&project_dir)?;
+                let engine_names = detect_engine_names(&project_root, &engine)?;
+                get_target_name_from_user(&plugin, names, engine_names)?
+            }
+        };
+        Ok(Self {
+            project_root,
+            plugin,
+            new_name,
+            scan_assets: false,
+            rewrite_assets: false,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine,
+        })
+    }
 }
 
 fn get_project_root_from_user() -> Result<PathBuf, String> {
-    Text::new("Project root directory path:")
-        .with_validator(validate_project_root_is_dir)
-        .with_validator(validate_project_root_contains_project_descriptor)
-        .with_validator(validate_project_root_contains_source_dir)
-        .prompt()
-        .map(|project_root| PathBuf::from(project_root))
-        .map_err(|err| err.to_string())
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor)
+            .with_validator(validate_project_root_contains_source_dir),
+    )
 }
 
 fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).is_dir() {
+    match project_root_of(Path::new(project_root)).is_dir() {
         true => Ok(Validation::Valid),
         false => {
-            let error_message = "Provided path is not a directory";
+            let error_message = "Provided path is not a directory or a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
     }
@@ -44,13 +83,9 @@ fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, Custom
 fn validate_project_root_contains_project_descriptor(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match fs::read_dir(project_root)?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(Validation::Valid),
-        false => {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
             let error_message = "Provided directory does not contain a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
@@ -60,7 +95,10 @@ fn validate_project_root_contains_project_descriptor(
 fn validate_project_root_contains_source_dir(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).join("Source").is_dir() {
+    match project_root_of(Path::new(project_root))
+        .join("Source")
+        .is_dir()
+    {
         true => Ok(Validation::Valid),
         false => {
             let error_message = "Provided directory does not contain a Source folder";
@@ -69,97 +107,42 @@ fn validate_project_root_contains_source_dir(
     }
 }
 
-/// Detect all plugins in a project given the path to the project root
-/// directory. Detects top-level plugins and nested plugins. Returns an error in
-/// case of I/O issues.
-fn detect_project_plugins(project_root: &PathBuf) -> Result<Vec<Plugin>, String> {
-    let plugins_dir = project_root.join("Plugins");
-    Ok(WalkDir::new(plugins_dir)
+fn get_target_plugin_from_user(project_root: &Path, plugins: &[Plugin]) -> Result<Plugin, String> {
+    let mut plugins = plugins.to_vec();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    let options = plugins
         .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map_or(false, |ext| ext == "uplugin")
-        })
-        .map(|entry| Plugin {
-            root: entry.path().parent().unwrap().to_owned(),
-            name: entry
-                .path()
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned(),
+        .map(|plugin| SelectOption {
+            label: format!(
+                "{}  [{}]",
+                plugin.name,
+                plugin
+                    .root
+                    .strip_prefix(project_root)
+                    .unwrap_or(&plugin.root)
+                    .display()
+            ),
+            item: plugin,
         })
-        .collect())
+        .collect();
+    select_from_user("Choose a plugin:", options)
 }
 
-fn get_target_plugin_from_user(plugins: &[Plugin]) -> Result<Plugin, String> {
-    Select::new("Choose a plugin:", plugins.to_vec())
-        .prompt()
-        .map_err(|err| err.to_string())
-}
-
-fn get_target_name_from_user(plugins: &[Plugin]) -> Result<String, String> {
-    let plugins = plugins.to_vec();
+fn get_target_name_from_user(
+    current_name: &str,
+    names: Vec<String>,
+    engine_names: Vec<String>,
+) -> Result<String, String> {
+    let current_name = current_name.to_owned();
     Text::new("Provide a new name for the plugin:")
-        .with_validator(validate_target_name_is_not_empty)
-        .with_validator(validate_target_name_is_concise)
-        .with_validator(move |input: &str| validate_target_name_is_unique(input, &plugins))
-        .with_validator(validate_target_name_is_valid_identifier)
+        .with_validator(move |input: &str| {
+            let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = validate_new_name(&current_name, input, NameKind::Plugin, &names);
+            into_prompt_validation(result)
+        })
+        .with_validator(move |input: &str| {
+            into_prompt_validation(validate_name_is_not_engine_name(input, &engine_names))
+        })
         .prompt()
         .map_err(|err| err.to_string())
 }
-
-fn validate_target_name_is_not_empty(target_name: &str) -> Result<Validation, CustomUserError> {
-    match !target_name.trim().is_empty() {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not be empty";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_concise(target_name: &str) -> Result<Validation, CustomUserError> {
-    let target_name_max_len = 30;
-    match target_name.len() <= target_name_max_len {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = format!(
-                "Target name must not be longer than {} characters",
-                target_name_max_len
-            );
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_unique(
-    target_name: &str,
-    plugins: &[Plugin],
-) -> Result<Validation, CustomUserError> {
-    match plugins.iter().all(|plugin| plugin.name != target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not conflict with another plugin";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_valid_identifier(
-    target_name: &str,
-) -> Result<Validation, CustomUserError> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message =
-                "Target name must be comprised of alphanumeric characters and underscores only";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
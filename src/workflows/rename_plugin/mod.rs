@@ -1,27 +1,54 @@
 mod changeset;
 mod interactive;
 
-use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{
+        detect_content_dirs, detect_module_and_plugin_names, detect_project_config_files,
+        detect_project_descriptor, detect_project_name, detect_project_plugins, project_root_of,
+    },
+    engine::Engine,
+    installation::{detect_engine_names, EngineOptions},
+    package::{
+        find_package_references, print_package_references, validate_new_name_fits_assets,
+        PackageReferences,
+    },
+    presentation::{
+        log,
+        prompt::{confirm_changeset_with_user, Confirmation},
+    },
+    unreal::Plugin,
+    validation::{validate_name_is_not_engine_name, validate_new_name, NameKind},
+    vcs::{check_working_tree, commit, commit_message, VcsOptions},
+    workflows::plan_clean,
 };
 
-use regex::Regex;
-use walkdir::WalkDir;
-
-use crate::{engine::Engine, presentation::log, unreal::Plugin};
-
-use self::{changeset::generate_changeset, interactive::get_params_from_user};
+use self::changeset::generate_changeset;
 
 /// Params needed to rename an Unreal Engine plugin.
 pub struct Params {
-    /// The root of the project.
+    /// The root of the project, or the path to its project descriptor.
     pub project_root: PathBuf,
     /// The specific plugin to rename.
     pub plugin: String,
     /// The new name for the plugin.
     pub new_name: String,
+    /// Whether to report assets that reference the plugin.
+    pub scan_assets: bool,
+    /// Whether to rewrite references to the plugin in assets. Requires the
+    /// new name to have the same length as the old name.
+    pub rewrite_assets: bool,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// How to work with the version control system of the project.
+    pub vcs: VcsOptions,
+    /// How to locate the engine that the project is built with.
+    pub engine: EngineOptions,
+    /// Whether to remove generated files that the rename leaves stale.
+    pub clean: bool,
 }
 
 /// Context needed to rename an Unreal Engine plugin.
@@ -32,26 +59,72 @@ pub struct Context {
     pub project_name: String,
     /// Plugins for the project.
     pub project_plugins: Vec<Plugin>,
+    /// Config files for the project and its plugins.
+    pub project_config_files: Vec<PathBuf>,
     /// The specific plugin to rename.
     pub plugin: Plugin,
     /// The new name for the plugin.
     pub new_name: String,
+    /// Assets that reference the plugin, if assets were scanned.
+    pub referencing_assets: Vec<PackageReferences>,
+    /// Whether to rewrite references to the plugin in assets.
+    pub rewrite_assets: bool,
 }
 
 /// Rename an Unreal Engine plugin interactively, soliciting input parameters
-/// from the user with validation and guided selection.
+/// from the user with validation and guided selection, and confirming the
+/// planned changes before applying them.
 pub fn rename_plugin_interactive() -> Result<(), String> {
-    let params = get_params_from_user()?;
-    rename_plugin(params)
+    let mut params = Params::from_user(None, None, None, EngineOptions::default())?;
+    loop {
+        match confirm_changeset_with_user(&plan_plugin_rename(&params)?)? {
+            Confirmation::Apply => return rename_plugin(params),
+            Confirmation::EditNewName => {
+                params = Params::from_user(
+                    Some(params.project_root),
+                    Some(params.plugin),
+                    None,
+                    params.engine,
+                )?
+            }
+            Confirmation::Cancel => return Ok(()),
+        }
+    }
+}
+
+/// Generate the changeset to rename an Unreal Engine plugin without applying
+/// it. Assumes that the plugin is part of the project.
+pub fn plan_plugin_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let context = gather_context(params)?;
+    Ok(generate_changeset(&context))
 }
 
 /// Rename an Unreal Engine plugin.
 pub fn rename_plugin(params: Params) -> Result<(), String> {
     validate_params(&params)?;
+    check_working_tree(&project_root_of(&params.project_root), &params.vcs)?;
     let context = gather_context(&params)?;
-    let changeset = generate_changeset(&context);
-    let backup_dir = create_backup_dir(&context.project_root)?;
-    let mut engine = Engine::new();
+    if params.scan_assets || params.rewrite_assets {
+        print_package_references(
+            &format!("/{}", context.plugin.name),
+            &context.referencing_assets,
+            context.rewrite_assets,
+        );
+    }
+    let mut changeset = generate_changeset(&context);
+    if params.clean {
+        changeset.extend(plan_clean(
+            &context.project_root,
+            &affected_plugin_roots(&context),
+        ));
+    }
+    let backup_dir = create_backup_dir(&context, params.backup_dir.as_deref())?;
+    let commit_message = commit_message(
+        &describe_operation(&context),
+        &context.project_root,
+        &changeset,
+    );
+    let mut engine = Engine::new().with_vcs(&params.vcs);
     if let Err(e) = engine.execute(changeset, backup_dir) {
         log::error(&e);
         engine.revert()?;
@@ -59,20 +132,30 @@ pub fn rename_plugin(params: Params) -> Result<(), String> {
         return Ok(());
     }
 
+    commit(
+        &engine.resolve(&context.project_root),
+        &params.vcs,
+        &commit_message,
+    )?;
     print_success_message(&context);
     Ok(())
 }
 
 fn validate_params(params: &Params) -> Result<(), String> {
-    validate_project_root_is_dir(&params.project_root)?;
-    validate_project_root_contains_project_descriptor(&params.project_root)?;
-    validate_project_root_contains_source_dir(&params.project_root)?;
-    let plugins = detect_project_plugins(&params.project_root)?;
+    let project_root = project_root_of(&params.project_root);
+    validate_project_root_is_dir(&project_root)?;
+    detect_project_descriptor(&params.project_root)?;
+    validate_project_root_contains_source_dir(&project_root)?;
+    let plugins = detect_project_plugins(&project_root)?;
     validate_plugin_exists(&params.plugin, &plugins)?;
-    validate_new_name_is_not_empty(&params.new_name)?;
-    validate_new_name_is_concise(&params.new_name)?;
-    validate_new_name_is_unique(&params.new_name, &plugins)?;
-    validate_new_name_is_valid_identifier(&params.new_name)?;
+    let names = detect_module_and_plugin_names(&project_root)?;
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    validate_new_name(&params.plugin, &params.new_name, NameKind::Plugin, &names)?;
+    let engine_names = detect_engine_names(&params.project_root, &params.engine)?;
+    validate_name_is_not_engine_name(&params.new_name, &engine_names)?;
+    if params.rewrite_assets {
+        validate_new_name_fits_assets(&params.new_name, &params.plugin)?;
+    }
     Ok(())
 }
 
@@ -83,18 +166,6 @@ fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
     }
 }
 
-fn validate_project_root_contains_project_descriptor(project_root: &Path) -> Result<(), String> {
-    match fs::read_dir(project_root)
-        .map_err(|e| e.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(()),
-        false => Err("project root must contain a project descriptor".into()),
-    }
-}
-
 fn validate_project_root_contains_source_dir(project_root: &Path) -> Result<(), String> {
     match project_root.join("Source").is_dir() {
         true => Ok(()),
@@ -109,113 +180,58 @@ fn validate_plugin_exists(plugin: &str, plugins: &[Plugin]) -> Result<(), String
     }
 }
 
-fn validate_new_name_is_not_empty(new_name: &str) -> Result<(), String> {
-    match !new_name.trim().is_empty() {
-        true => Ok(()),
-        false => Err("new name must not be empty".into()),
-    }
-}
-
-fn validate_new_name_is_concise(new_name: &str) -> Result<(), String> {
-    let new_name_max_len = 30;
-    match new_name.len() <= new_name_max_len {
-        true => Ok(()),
-        false => {
-            let error_message = format!(
-                "new name must not be longer than {} characters",
-                new_name_max_len
-            );
-            Err(error_message)
-        }
-    }
-}
-
-fn validate_new_name_is_unique(new_name: &str, plugins: &[Plugin]) -> Result<(), String> {
-    match plugins.iter().all(|plugin| plugin.name != new_name) {
-        true => Ok(()),
-        false => {
-            let error_message = "new name must not conflict with another plugin";
-            Err(error_message.into())
-        }
-    }
-}
-
-fn validate_new_name_is_valid_identifier(new_name: &str) -> Result<(), String> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(new_name) {
-        true => Ok(()),
-        false => {
-            let error_message =
-                "new name must be comprised of alphanumeric characters and underscores only";
-            Err(error_message.into())
-        }
-    }
-}
-
 fn gather_context(params: &Params) -> Result<Context, String> {
+    let project_root = project_root_of(&params.project_root);
     let project_name = detect_project_name(&params.project_root)?;
-    let project_plugins = detect_project_plugins(&params.project_root)?;
+    let project_plugins = detect_project_plugins(&project_root)?;
     let plugin = project_plugins
         .iter()
         .find(|plugin| plugin.name == params.plugin)
         .unwrap()
         .clone();
+    let referencing_assets = match params.scan_assets || params.rewrite_assets {
+        true => find_package_references(
+            &detect_content_dirs(&project_root)?,
+            &format!("/{}", plugin.name),
+        )?,
+        false => vec![],
+    };
 
     Ok(Context {
-        project_root: params.project_root.clone(),
+        project_config_files: detect_project_config_files(&project_root)?,
+        project_root,
         project_name,
         project_plugins,
         plugin,
         new_name: params.new_name.clone(),
+        referencing_assets,
+        rewrite_assets: params.rewrite_assets,
     })
 }
 
-fn detect_project_name(project_root: &PathBuf) -> Result<String, String> {
-    assert!(project_root.is_dir());
-
-    let project_descriptor = fs::read_dir(project_root)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| path.extension().map_or(false, |ext| ext == "uproject"))
-        .next()
-        .expect("project descriptor should exist");
-
-    project_descriptor
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-        .map(|name| name.to_owned())
-        .ok_or("project name is not valid Unicode".into())
+/// The roots of the plugins whose build products are stale after the rename,
+/// which are the plugin itself and any plugins nested in it.
+fn affected_plugin_roots(context: &Context) -> Vec<PathBuf> {
+    context
+        .project_plugins
+        .iter()
+        .filter(|plugin| plugin.root.starts_with(&context.plugin.root))
+        .map(|plugin| plugin.root.clone())
+        .collect()
 }
 
-fn detect_project_plugins(project_root: &PathBuf) -> Result<Vec<Plugin>, String> {
-    let plugins_dir = project_root.join("Plugins");
-    Ok(WalkDir::new(plugins_dir)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map_or(false, |ext| ext == "uplugin")
-        })
-        .map(|entry| Plugin {
-            root: entry.path().parent().unwrap().to_owned(),
-            name: entry
-                .path()
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_owned(),
-        })
-        .collect())
+/// Describe the operation for backup sets and commit messages.
+fn describe_operation(context: &Context) -> String {
+    format!(
+        "rename plugin {} to {}",
+        context.plugin.name, context.new_name
+    )
 }
 
-fn create_backup_dir(project_root: &Path) -> Result<PathBuf, String> {
-    let backup_dir = project_root.join(".renom/backup");
-    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
-    Ok(backup_dir)
+fn create_backup_dir(context: &Context, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let operation = describe_operation(context);
+    let backup_root = backup_root(&context.project_root, backup_dir)?;
+    create_backup_set(&backup_root, &operation)
 }
 
 fn print_success_message(context: &Context) {
@@ -4,7 +4,9 @@ use crate::changes::{Change, RenameFile, SetIniEntry};
 
 use super::Context;
 
-/// Generate a changeset to rename an Unreal Engine project.
+/// Generate a changeset to rename an Unreal Engine project. The project root
+/// is renamed last, so that every other change is made before the directory
+/// that contains it moves.
 pub fn generate_changeset(context: &Context) -> Vec<Change> {
     let Context {
         project_root,
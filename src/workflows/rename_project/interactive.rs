@@ -1,130 +1,93 @@
-use std::{ffi::OsStr, fs, path::PathBuf};
+use std::path::{Path, PathBuf};
 
 use inquire::{validator::Validation, CustomUserError, Text};
-use regex::Regex;
+
+use crate::{
+    detection::{detect_project_name, find_project_descriptors, project_root_of},
+    installation::{detect_engine_names, EngineOptions},
+    presentation::prompt::get_project_path_from_user,
+    validation::{
+        into_prompt_validation, validate_name_is_not_engine_name, validate_new_name, NameKind,
+    },
+};
 
 use super::Params;
-use indoc::indoc;
 
-pub fn get_params_from_user() -> Result<Params, String> {
-    let project_root = get_project_root_from_user()?;
-    let target_name = get_target_name_from_user()?;
-    Ok(Params {
-        project_root,
-        new_name: target_name,
-    })
+impl Params {
+    /// Build params from whichever of the project root and new name are
+    /// given, soliciting the rest from the user. The engine options are used
+    /// to check new names against the names of the engine.
+    pub fn from_user(
+        project_root: Option<PathBuf>,
+        new_name: Option<String>,
+        engine: EngineOptions,
+    ) -> Result<Self, String> {
+        let project_root = match project_root {
+            Some(project_root) => project_root,
+            None => get_project_root_from_user()?,
+        };
+        let new_name = match new_name {
+            Some(new_name) => new_name,
+            None => {
+                let project_name = detect_project_name(&project_root)?;
+                let engine_names = detect_engine_names(&project_root, &engine)?;
+                get_target_name_from_user(&project_name, engine_names)?
+            }
+        };
+        Ok(Self {
+            project_root,
+            new_name,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine,
+        })
+    }
 }
 
 fn get_project_root_from_user() -> Result<PathBuf, String> {
-    Text::new("Project root directory path:")
-        .with_validator(validate_project_root_is_not_special)
-        .with_validator(validate_project_root_is_dir)
-        .with_validator(validate_project_root_is_not_current_dir)
-        .with_validator(validate_project_root_contains_project_descriptor)
-        .prompt()
-        .map(|project_root| PathBuf::from(project_root))
-        .map_err(|err| err.to_string())
-}
-
-fn validate_project_root_is_not_special(project_root: &str) -> Result<Validation, CustomUserError> {
-    match project_root {
-        "." => Ok(Validation::Invalid("Provided path '.' is protected".into())),
-        ".." => Ok(Validation::Invalid(
-            "Provided path '..' is protected".into(),
-        )),
-        _ => Ok(Validation::Valid),
-    }
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor),
+    )
 }
 
 fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).is_dir() {
+    match project_root_of(Path::new(project_root)).is_dir() {
         true => Ok(Validation::Valid),
         false => {
-            let error_message = "Provided path is not a directory";
+            let error_message = "Provided path is not a directory or a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
     }
 }
 
-fn validate_project_root_is_not_current_dir(
-    project_root: &str,
-) -> Result<Validation, CustomUserError> {
-    let project_root = PathBuf::from(project_root);
-    let current_dir = std::env::current_dir().map_err(|err| err.to_string())?;
-    let abs_current_dir = fs::canonicalize(current_dir).map_err(|err| err.to_string())?;
-    let abs_project_root = fs::canonicalize(project_root).map_err(|err| err.to_string())?;
-
-    if abs_project_root == abs_current_dir {
-        let error_message = indoc! {"
-            Provided directory is the current directory which is protected
-              Move to the parent directory and try again\
-        "};
-
-        return Ok(Validation::Invalid(error_message.into()));
-    }
-
-    Ok(Validation::Valid)
-}
-
 fn validate_project_root_contains_project_descriptor(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match fs::read_dir(project_root)?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(Validation::Valid),
-        false => {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
             let error_message = "Provided directory does not contain a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
     }
 }
 
-fn get_target_name_from_user() -> Result<String, String> {
+fn get_target_name_from_user(
+    project_name: &str,
+    engine_names: Vec<String>,
+) -> Result<String, String> {
+    let project_name = project_name.to_owned();
     Text::new("Provide a new name for the project:")
-        .with_validator(validate_target_name_is_not_empty)
-        .with_validator(validate_target_name_is_concise)
-        .with_validator(validate_target_name_is_valid_identifier)
+        .with_validator(move |input: &str| {
+            let result = validate_new_name(&project_name, input, NameKind::Project, &[]);
+            into_prompt_validation(result)
+        })
+        .with_validator(move |input: &str| {
+            into_prompt_validation(validate_name_is_not_engine_name(input, &engine_names))
+        })
         .prompt()
         .map_err(|err| err.to_string())
 }
-
-fn validate_target_name_is_not_empty(target_name: &str) -> Result<Validation, CustomUserError> {
-    match !target_name.trim().is_empty() {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not be empty";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_concise(target_name: &str) -> Result<Validation, CustomUserError> {
-    let target_name_max_len = 20;
-    match target_name.len() <= target_name_max_len {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = format!(
-                "Target name must not be longer than {} characters",
-                target_name_max_len
-            );
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_valid_identifier(
-    target_name: &str,
-) -> Result<Validation, CustomUserError> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message =
-                "Target name must be comprised of alphanumeric characters and underscores only";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
@@ -2,23 +2,41 @@ mod changeset;
 mod interactive;
 
 use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
+    env,
+    path::{Component, Path, PathBuf},
 };
 
-use regex::Regex;
-
-use crate::{engine::Engine, presentation::log};
+use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{detect_project_descriptor, detect_project_name, project_root_of},
+    engine::Engine,
+    installation::{detect_engine_names, EngineOptions},
+    presentation::{
+        log,
+        prompt::{confirm_changeset_with_user, Confirmation},
+    },
+    validation::{validate_name_is_not_engine_name, validate_new_name, NameKind},
+    vcs::{check_working_tree, commit, commit_message, VcsOptions},
+    workflows::plan_clean,
+};
 
-use self::{changeset::generate_changeset, interactive::get_params_from_user};
+use self::changeset::generate_changeset;
 
 /// Params needed to rename an Unreal Engine project.
 pub struct Params {
-    /// The root of the project.
+    /// The root of the project, or the path to its project descriptor.
     pub project_root: PathBuf,
     /// The new name for the project.
     pub new_name: String,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// How to work with the version control system of the project.
+    pub vcs: VcsOptions,
+    /// How to locate the engine that the project is built with.
+    pub engine: EngineOptions,
+    /// Whether to remove generated files that the rename leaves stale.
+    pub clean: bool,
 }
 
 /// Context needed to rename an Unreal Engine project.
@@ -32,51 +50,86 @@ pub struct Context {
 }
 
 /// Rename an Unreal Engine project interactively, soliciting input parameters
-/// from the user with validation and guided selection.
+/// from the user with validation and guided selection, and confirming the
+/// planned changes before applying them.
 pub fn rename_project_interactive() -> Result<(), String> {
-    let params = get_params_from_user()?;
-    rename_project(params)
+    let mut params = Params::from_user(None, None, EngineOptions::default())?;
+    loop {
+        match confirm_changeset_with_user(&plan_project_rename(&params)?)? {
+            Confirmation::Apply => return rename_project(params),
+            Confirmation::EditNewName => {
+                params = Params::from_user(Some(params.project_root), None, params.engine)?
+            }
+            Confirmation::Cancel => return Ok(()),
+        }
+    }
+}
+
+/// Generate the changeset to rename an Unreal Engine project without applying
+/// it.
+pub fn plan_project_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let context = gather_context(params)?;
+    Ok(generate_changeset(&context))
 }
 
 /// Rename an Unreal Engine project.
 pub fn rename_project(params: Params) -> Result<(), String> {
     validate_params(&params)?;
+    check_working_tree(&project_root_of(&params.project_root), &params.vcs)?;
     let context = gather_context(&params)?;
-    let changeset = generate_changeset(&context);
-    let backup_dir = create_backup_dir(&context.project_root)?;
-    let mut engine = Engine::new();
+    let mut changeset = generate_changeset(&context);
+    if params.clean {
+        // Clean before the project root is renamed, which comes last.
+        let root_rename = changeset.len() - 1;
+        changeset.splice(
+            root_rename..root_rename,
+            plan_clean(&context.project_root, &[]),
+        );
+    }
+    let backup_dir = create_backup_dir(&context, params.backup_dir.as_deref())?;
+    let commit_message = commit_message(
+        &describe_operation(&context),
+        &context.project_root,
+        &changeset,
+    );
+    let current_dir_in_project = current_dir_in_project(&context.project_root)?;
+    let mut engine = Engine::new().with_vcs(&params.vcs);
     if let Err(e) = engine.execute(changeset, backup_dir) {
         log::error(&e);
         engine.revert()?;
         print_failure_message(&context);
+        if current_dir_in_project.is_some() {
+            log::basic(
+                "A directory in use cannot be renamed on some platforms, \
+                so run the rename from outside of the project.",
+            );
+        }
         return Ok(());
     }
 
+    let new_project_root = engine.resolve(&context.project_root);
+    commit(&new_project_root, &params.vcs, &commit_message)?;
     print_success_message(&context);
+    if let Some(current_dir) = &current_dir_in_project {
+        log::basic(format!(
+            "Run `cd {}` to continue working in the renamed project.",
+            new_project_root.join(current_dir).display()
+        ));
+    }
     Ok(())
 }
 
 fn validate_params(params: &Params) -> Result<(), String> {
-    validate_project_root_is_not_special(&params.project_root)?;
-    validate_project_root_is_dir(&params.project_root)?;
-    validate_project_root_is_not_current_dir(&params.project_root)?;
-    validate_project_root_contains_project_descriptor(&params.project_root)?;
+    let project_root = project_root_of(&params.project_root);
+    validate_project_root_is_dir(&project_root)?;
+    detect_project_descriptor(&params.project_root)?;
     let project_name = detect_project_name(&params.project_root)?;
-    validate_new_name_is_not_empty(&params.new_name)?;
-    validate_new_name_is_novel(&project_name, &params.new_name)?;
-    validate_new_name_is_concise(&params.new_name)?;
-    validate_new_name_is_valid_identifier(&params.new_name)?;
+    validate_new_name(&project_name, &params.new_name, NameKind::Project, &[])?;
+    let engine_names = detect_engine_names(&params.project_root, &params.engine)?;
+    validate_name_is_not_engine_name(&params.new_name, &engine_names)?;
     Ok(())
 }
 
-fn validate_project_root_is_not_special(project_root: &Path) -> Result<(), String> {
-    match project_root {
-        path if path == Path::new(".") => Err("project root cannot be '.'".into()),
-        path if path == Path::new("..") => Err("project root cannot be '..'".into()),
-        _ => Ok(()),
-    }
-}
-
 fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
     match project_root.is_dir() {
         true => Ok(()),
@@ -84,102 +137,59 @@ fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
     }
 }
 
-fn validate_project_root_is_not_current_dir(project_root: &Path) -> Result<(), String> {
-    let current_dir = std::env::current_dir().map_err(|err| err.to_string())?;
-    let abs_current_dir = fs::canonicalize(current_dir).map_err(|err| err.to_string())?;
-    let abs_project_root = fs::canonicalize(project_root).map_err(|err| err.to_string())?;
-
-    if abs_project_root == abs_current_dir {
-        return Err("project root cannot be current directory".into());
-    }
-
-    Ok(())
-}
-
-fn validate_project_root_contains_project_descriptor(project_root: &Path) -> Result<(), String> {
-    match fs::read_dir(&project_root)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(()),
-        false => Err("project root must contain a project descriptor".into()),
-    }
-}
-
-fn validate_new_name_is_novel(old_name: &str, new_name: &str) -> Result<(), String> {
-    match old_name != new_name {
-        true => Ok(()),
-        false => Err("new name must be different than current name".into()),
-    }
-}
-
-fn validate_new_name_is_not_empty(new_name: &str) -> Result<(), String> {
-    match !new_name.trim().is_empty() {
-        true => Ok(()),
-        false => Err("new name must not be empty".into()),
-    }
+fn gather_context(params: &Params) -> Result<Context, String> {
+    let project_name = detect_project_name(&params.project_root)?;
+    Ok(Context {
+        project_root: resolve_project_root(&project_root_of(&params.project_root))?,
+        project_name,
+        new_name: params.new_name.clone(),
+    })
 }
 
-fn validate_new_name_is_concise(new_name: &str) -> Result<(), String> {
-    let new_name_max_len = 20;
-    match new_name.len() <= new_name_max_len {
-        true => Ok(()),
-        false => {
-            let error_message = format!(
-                "new name must not be longer than {} characters",
-                new_name_max_len
-            );
-            Err(error_message)
+/// Resolve the project root to an absolute path without `.` or `..`
+/// components, so that the root has a name to rename even when it is given
+/// as the current directory.
+fn resolve_project_root(project_root: &Path) -> Result<PathBuf, String> {
+    let current_dir = env::current_dir().map_err(|err| err.to_string())?;
+    let mut resolved = PathBuf::new();
+    for component in current_dir.join(project_root).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
         }
     }
-}
-
-fn validate_new_name_is_valid_identifier(new_name: &str) -> Result<(), String> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(new_name) {
-        true => Ok(()),
-        false => {
-            let error_message =
-                "new name must be comprised of alphanumeric characters and underscores only";
-            Err(error_message.into())
-        }
+    match resolved.parent() {
+        Some(_) => Ok(resolved),
+        None => Err("project root cannot be the root of the file system".into()),
     }
 }
 
-fn gather_context(params: &Params) -> Result<Context, String> {
-    let project_name = detect_project_name(&PathBuf::from(&params.project_root))?;
-    Ok(Context {
-        project_root: params.project_root.clone(),
-        project_name,
-        new_name: params.new_name.clone(),
-    })
+/// The current directory relative to the project root, if the current
+/// directory is inside of the project.
+fn current_dir_in_project(project_root: &Path) -> Result<Option<PathBuf>, String> {
+    let current_dir = env::current_dir().map_err(|err| err.to_string())?;
+    Ok(current_dir
+        .strip_prefix(project_root)
+        .ok()
+        .map(Path::to_owned))
 }
 
-fn detect_project_name(project_root: &PathBuf) -> Result<String, String> {
-    assert!(project_root.is_dir());
-
-    let project_descriptor = fs::read_dir(project_root)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| path.extension().map_or(false, |ext| ext == "uproject"))
-        .next()
-        .expect("project descriptor should exist");
-
-    project_descriptor
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-        .map(|name| name.to_owned())
-        .ok_or("project name is not valid Unicode".into())
+/// Describe the operation for backup sets and commit messages.
+fn describe_operation(context: &Context) -> String {
+    format!(
+        "rename project {} to {}",
+        context.project_name, context.new_name
+    )
 }
 
 /// Create a directory to store backup files in
-fn create_backup_dir(project_root: &Path) -> Result<PathBuf, String> {
-    let backup_dir = project_root.join(".renom/backup");
-    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
-    Ok(backup_dir)
+fn create_backup_dir(context: &Context, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let operation = describe_operation(context);
+    let backup_root = backup_root(&context.project_root, backup_dir)?;
+    create_backup_set(&backup_root, &operation)
 }
 
 fn print_success_message(context: &Context) {
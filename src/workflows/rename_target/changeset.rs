@@ -11,10 +11,12 @@ use super::Context;
 pub fn generate_changeset(context: &Context) -> Vec<Change> {
     let Context {
         project_targets,
-        target: Target {
-            name: old_name,
-            path: target_file,
-        },
+        target:
+            Target {
+                name: old_name,
+                path: target_file,
+                ..
+            },
         new_name,
         ..
     } = context;
@@ -1,43 +1,72 @@
-use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
-use inquire::{validator::Validation, CustomUserError, Select, Text};
-use regex::Regex;
+use inquire::{validator::Validation, CustomUserError, Text};
 
-use crate::unreal::Target;
+use crate::{
+    detection::{detect_project_targets, find_project_descriptors, project_root_of},
+    installation::{detect_engine_names, EngineOptions},
+    presentation::prompt::{get_project_path_from_user, select_from_user, SelectOption},
+    unreal::Target,
+    validation::{
+        into_prompt_validation, validate_name_is_not_engine_name, validate_new_name, NameKind,
+    },
+};
 
 use super::Params;
 
-pub fn get_params_from_user() -> Result<Params, String> {
-    let project_root = get_project_root_from_user()?;
-    let project_targets = detect_project_targets(&project_root)?;
-    let target_target = get_target_target_from_user(&project_targets)?;
-    let target_name = get_target_name_from_user(&project_targets)?;
-    Ok(Params {
-        project_root,
-        target: target_target.name,
-        new_name: target_name,
-    })
+impl Params {
+    /// Build params from whichever of the project root, target and new name
+    /// are given, soliciting the rest from the user. The engine options are
+    /// used to check new names against the names of the engine.
+    pub fn from_user(
+        project_root: Option<PathBuf>,
+        target: Option<String>,
+        new_name: Option<String>,
+        engine: EngineOptions,
+    ) -> Result<Self, String> {
+        let project_root = match project_root {
+            Some(project_root) => project_root,
+            None => get_project_root_from_user()?,
+        };
+        let project_dir = project_root_of(&project_root);
+        let target = match target {
+            Some(target) => target,
+            None => get_target_target_from_user(&detect_project_targets(&project_dir)?)?.name,
+        };
+        let new_name = match new_name {
+            Some(new_name) => new_name,
+            None => {
+                let targets = detect_project_targets(&project_dir)?;
+                let engine_names = detect_engine_names(&project_root, &engine)?;
+                get_target_name_from_user(&target, &targets, engine_names)?
+            }
+        };
+        Ok(Self {
+            project_root,
+            target,
+            new_name,
+            backup_dir: None,
+            clean: false,
+            vcs: Default::default(),
+            engine,
+        })
+    }
 }
 
 fn get_project_root_from_user() -> Result<PathBuf, String> {
-    Text::new("Project root directory path:")
-        .with_validator(validate_project_root_is_dir)
-        .with_validator(validate_project_root_contains_project_descriptor)
-        .with_validator(validate_project_root_contains_source_dir)
-        .prompt()
-        .map(|project_root| PathBuf::from(project_root))
-        .map_err(|err| err.to_string())
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor)
+            .with_validator(validate_project_root_contains_source_dir),
+    )
 }
 
 fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).is_dir() {
+    match project_root_of(Path::new(project_root)).is_dir() {
         true => Ok(Validation::Valid),
         false => {
-            let error_message = "Provided path is not a directory";
+            let error_message = "Provided path is not a directory or a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
     }
@@ -46,13 +75,9 @@ fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, Custom
 fn validate_project_root_contains_project_descriptor(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match fs::read_dir(project_root)?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(Validation::Valid),
-        false => {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
             let error_message = "Provided directory does not contain a .uproject file";
             Ok(Validation::Invalid(error_message.into()))
         }
@@ -62,7 +87,10 @@ fn validate_project_root_contains_project_descriptor(
 fn validate_project_root_contains_source_dir(
     project_root: &str,
 ) -> Result<Validation, CustomUserError> {
-    match PathBuf::from(project_root).join("Source").is_dir() {
+    match project_root_of(Path::new(project_root))
+        .join("Source")
+        .is_dir()
+    {
         true => Ok(Validation::Valid),
         false => {
             let error_message = "Provided directory does not contain a Source folder";
@@ -71,91 +99,41 @@ fn validate_project_root_contains_source_dir(
     }
 }
 
-fn detect_project_targets(project_root: &Path) -> Result<Vec<Target>, String> {
-    let source_dir = project_root.join("Source");
-    assert!(source_dir.is_dir());
-    Ok(fs::read_dir(&source_dir)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| {
-            entry
-                .path()
-                .file_name()
-                .and_then(|file_name| file_name.to_str())
-                .and_then(|str| str.strip_suffix(".Target.cs"))
-                .map(|str| str.to_owned())
-        })
-        .map(|target_name| Target {
-            name: target_name.clone(),
-            path: source_dir.join(target_name).with_extension("Target.cs"),
-        })
-        .collect())
-}
-
 fn get_target_target_from_user(targets: &[Target]) -> Result<Target, String> {
-    Select::new("Choose a target:", targets.to_vec())
-        .prompt()
-        .map_err(|err| err.to_string())
+    let mut targets = targets.to_vec();
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    let options = targets
+        .into_iter()
+        .map(|target| SelectOption {
+            label: match target.r#type {
+                Some(r#type) => format!("{}  [{}]", target.name, r#type),
+                None => format!("{}  [unknown type]", target.name),
+            },
+            item: target,
+        })
+        .collect();
+    select_from_user("Choose a target:", options)
 }
 
-fn get_target_name_from_user(targets: &[Target]) -> Result<String, String> {
-    let targets = targets.to_vec();
+fn get_target_name_from_user(
+    current_name: &str,
+    targets: &[Target],
+    engine_names: Vec<String>,
+) -> Result<String, String> {
+    let current_name = current_name.to_owned();
+    let names = targets
+        .iter()
+        .map(|target| target.name.clone())
+        .collect::<Vec<_>>();
     Text::new("Provide a new name for the target:")
-        .with_validator(validate_target_name_is_not_empty)
-        .with_validator(validate_target_name_is_concise)
-        .with_validator(move |input: &str| validate_target_name_is_unique(input, &targets))
-        .with_validator(validate_target_name_is_valid_identifier)
+        .with_validator(move |input: &str| {
+            let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = validate_new_name(&current_name, input, NameKind::Target, &names);
+            into_prompt_validation(result)
+        })
+        .with_validator(move |input: &str| {
+            into_prompt_validation(validate_name_is_not_engine_name(input, &engine_names))
+        })
         .prompt()
         .map_err(|err| err.to_string())
 }
-
-fn validate_target_name_is_not_empty(target_name: &str) -> Result<Validation, CustomUserError> {
-    match !target_name.trim().is_empty() {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not be empty";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_concise(target_name: &str) -> Result<Validation, CustomUserError> {
-    let target_name_max_len = 30;
-    match target_name.len() <= target_name_max_len {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = format!(
-                "Target name must not be longer than {} characters",
-                target_name_max_len
-            );
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_unique(
-    target_name: &str,
-    targets: &[Target],
-) -> Result<Validation, CustomUserError> {
-    match targets.iter().all(|target| target.name != target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message = "Target name must not conflict with another target";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
-
-fn validate_target_name_is_valid_identifier(
-    target_name: &str,
-) -> Result<Validation, CustomUserError> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(target_name) {
-        true => Ok(Validation::Valid),
-        false => {
-            let error_message =
-                "Target name must be comprised of alphanumeric characters and underscores only";
-            Ok(Validation::Invalid(error_message.into()))
-        }
-    }
-}
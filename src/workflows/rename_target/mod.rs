@@ -1,26 +1,42 @@
 mod changeset;
 mod interactive;
 
-use std::{
-    ffi::OsStr,
-    fs,
-    path::{Path, PathBuf},
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backup::{backup_root, create_backup_set},
+    changes::Change,
+    detection::{detect_project_descriptor, detect_project_targets, project_root_of},
+    engine::Engine,
+    installation::{detect_engine_names, EngineOptions},
+    presentation::{
+        log,
+        prompt::{confirm_changeset_with_user, Confirmation},
+    },
+    unreal::Target,
+    validation::{validate_name_is_not_engine_name, validate_new_name, NameKind},
+    vcs::{check_working_tree, commit, commit_message, VcsOptions},
+    workflows::plan_clean,
 };
 
-use regex::Regex;
-
-use crate::{engine::Engine, presentation::log, unreal::Target};
-
-use self::{changeset::generate_changeset, interactive::get_params_from_user};
+use self::changeset::generate_changeset;
 
 /// Params needed to rename an Unreal Engine target.
 pub struct Params {
-    /// The root of the project.
+    /// The root of the project, or the path to its project descriptor.
     pub project_root: PathBuf,
     /// The specific target to rename.
     pub target: String,
     /// The new name for the target.
     pub new_name: String,
+    /// Where to store backups, defaulting to a per-user data directory.
+    pub backup_dir: Option<PathBuf>,
+    /// How to work with the version control system of the project.
+    pub vcs: VcsOptions,
+    /// How to locate the engine that the project is built with.
+    pub engine: EngineOptions,
+    /// Whether to remove generated files that the rename leaves stale.
+    pub clean: bool,
 }
 
 /// Context needed to rename an Unreal Engine target.
@@ -36,19 +52,49 @@ pub struct Context {
 }
 
 /// Rename an Unreal Engine target interactively, soliciting input parameters
-/// from the user with validation and guided selection.
+/// from the user with validation and guided selection, and confirming the
+/// planned changes before applying them.
 pub fn rename_target_interactive() -> Result<(), String> {
-    let params = get_params_from_user()?;
-    rename_target(params)
+    let mut params = Params::from_user(None, None, None, EngineOptions::default())?;
+    loop {
+        match confirm_changeset_with_user(&plan_target_rename(&params)?)? {
+            Confirmation::Apply => return rename_target(params),
+            Confirmation::EditNewName => {
+                params = Params::from_user(
+                    Some(params.project_root),
+                    Some(params.target),
+                    None,
+                    params.engine,
+                )?
+            }
+            Confirmation::Cancel => return Ok(()),
+        }
+    }
+}
+
+/// Generate the changeset to rename an Unreal Engine target without applying
+/// it. Assumes that the target is part of the project.
+pub fn plan_target_rename(params: &Params) -> Result<Vec<Change>, String> {
+    let context = gather_context(params)?;
+    Ok(generate_changeset(&context))
 }
 
 /// Rename an Unreal Engine target.
 pub fn rename_target(params: Params) -> Result<(), String> {
     validate_params(&params)?;
+    check_working_tree(&project_root_of(&params.project_root), &params.vcs)?;
     let context = gather_context(&params)?;
-    let changeset = generate_changeset(&context);
-    let backup_dir = create_backup_dir(&context.project_root)?;
-    let mut engine = Engine::new();
+    let mut changeset = generate_changeset(&context);
+    if params.clean {
+        changeset.extend(plan_clean(&context.project_root, &[]));
+    }
+    let backup_dir = create_backup_dir(&context, params.backup_dir.as_deref())?;
+    let commit_message = commit_message(
+        &describe_operation(&context),
+        &context.project_root,
+        &changeset,
+    );
+    let mut engine = Engine::new().with_vcs(&params.vcs);
     if let Err(e) = engine.execute(changeset, backup_dir) {
         log::error(&e);
         engine.revert()?;
@@ -56,20 +102,29 @@ pub fn rename_target(params: Params) -> Result<(), String> {
         return Ok(());
     }
 
+    commit(
+        &engine.resolve(&context.project_root),
+        &params.vcs,
+        &commit_message,
+    )?;
     print_success_message(&context);
     Ok(())
 }
 
 fn validate_params(params: &Params) -> Result<(), String> {
-    validate_project_root_is_dir(&params.project_root)?;
-    validate_project_root_contains_project_descriptor(&params.project_root)?;
-    validate_project_root_contains_source_dir(&params.project_root)?;
-    let targets = detect_project_targets(&params.project_root)?;
+    let project_root = project_root_of(&params.project_root);
+    validate_project_root_is_dir(&project_root)?;
+    detect_project_descriptor(&params.project_root)?;
+    validate_project_root_contains_source_dir(&project_root)?;
+    let targets = detect_project_targets(&project_root)?;
     validate_target_exists(&params.target, &targets)?;
-    validate_new_name_is_not_empty(&params.new_name)?;
-    validate_new_name_is_concise(&params.new_name)?;
-    validate_new_name_is_unique(&params.new_name, &targets)?;
-    validate_new_name_is_valid_identifier(&params.new_name)?;
+    let names = targets
+        .iter()
+        .map(|target| target.name.as_str())
+        .collect::<Vec<_>>();
+    validate_new_name(&params.target, &params.new_name, NameKind::Target, &names)?;
+    let engine_names = detect_engine_names(&params.project_root, &params.engine)?;
+    validate_name_is_not_engine_name(&params.new_name, &engine_names)?;
     Ok(())
 }
 
@@ -80,18 +135,6 @@ fn validate_project_root_is_dir(project_root: &Path) -> Result<(), String> {
     }
 }
 
-fn validate_project_root_contains_project_descriptor(project_root: &Path) -> Result<(), String> {
-    match fs::read_dir(project_root)
-        .map_err(|e| e.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| entry.path().extension().map(OsStr::to_owned))
-        .any(|ext| ext == "uproject")
-    {
-        true => Ok(()),
-        false => Err("project root must contain a project descriptor".into()),
-    }
-}
-
 fn validate_project_root_contains_source_dir(project_root: &Path) -> Result<(), String> {
     match project_root.join("Source").is_dir() {
         true => Ok(()),
@@ -106,72 +149,8 @@ fn validate_target_exists(target: &str, targets: &[Target]) -> Result<(), String
     }
 }
 
-fn validate_new_name_is_not_empty(new_name: &str) -> Result<(), String> {
-    match !new_name.trim().is_empty() {
-        true => Ok(()),
-        false => Err("new name must not be empty".into()),
-    }
-}
-
-fn validate_new_name_is_concise(new_name: &str) -> Result<(), String> {
-    let new_name_max_len = 30;
-    match new_name.len() <= new_name_max_len {
-        true => Ok(()),
-        false => {
-            let error_message = format!(
-                "new name must not be longer than {} characters",
-                new_name_max_len
-            );
-            Err(error_message)
-        }
-    }
-}
-
-fn validate_new_name_is_unique(new_name: &str, targets: &[Target]) -> Result<(), String> {
-    match targets.iter().all(|target| target.name != new_name) {
-        true => Ok(()),
-        false => {
-            let error_message = "new name must not conflict with another target";
-            Err(error_message.into())
-        }
-    }
-}
-
-fn validate_new_name_is_valid_identifier(new_name: &str) -> Result<(), String> {
-    let identifier_regex = Regex::new("^[_[[:alnum:]]]*$").expect("regex should be valid");
-    match identifier_regex.is_match(new_name) {
-        true => Ok(()),
-        false => {
-            let error_message =
-                "new name must be comprised of alphanumeric characters and underscores only";
-            Err(error_message.into())
-        }
-    }
-}
-
-fn detect_project_targets(project_root: &Path) -> Result<Vec<Target>, String> {
-    let source_dir = project_root.join("Source");
-    assert!(source_dir.is_dir());
-    Ok(fs::read_dir(&source_dir)
-        .map_err(|err| err.to_string())?
-        .filter_map(Result::ok)
-        .filter_map(|entry| {
-            entry
-                .path()
-                .file_name()
-                .and_then(|file_name| file_name.to_str())
-                .and_then(|str| str.strip_suffix(".Target.cs"))
-                .map(|str| str.to_owned())
-        })
-        .map(|target_name| Target {
-            name: target_name.clone(),
-            path: source_dir.join(target_name).with_extension("Target.cs"),
-        })
-        .collect())
-}
-
 fn gather_context(params: &Params) -> Result<Context, String> {
-    let project_root = params.project_root.clone();
+    let project_root = project_root_of(&params.project_root);
     let project_targets = detect_project_targets(&project_root)?;
     let target = project_targets
         .iter()
@@ -187,10 +166,18 @@ fn gather_context(params: &Params) -> Result<Context, String> {
     })
 }
 
-fn create_backup_dir(project_root: &Path) -> Result<PathBuf, String> {
-    let backup_dir = project_root.join(".renom/backup");
-    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
-    Ok(backup_dir)
+/// Describe the operation for backup sets and commit messages.
+fn describe_operation(context: &Context) -> String {
+    format!(
+        "rename target {} to {}",
+        context.target.name, context.new_name
+    )
+}
+
+fn create_backup_dir(context: &Context, backup_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let operation = describe_operation(context);
+    let backup_root = backup_root(&context.project_root, backup_dir)?;
+    create_backup_set(&backup_root, &operation)
 }
 
 fn print_success_message(context: &Context) {
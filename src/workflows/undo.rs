@@ -0,0 +1,112 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use inquire::{validator::Validation, Confirm, CustomUserError, Text};
+
+use crate::{
+    backup::{find_backup_root, find_backup_set, find_backup_sets, restore_backup_set, Manifest},
+    detection::{find_project_descriptors, project_root_of},
+    presentation::{
+        log,
+        prompt::{get_project_path_from_user, select_from_user, SelectOption},
+    },
+};
+
+use super::{format_age, print_restored_entries};
+
+/// Undo an earlier operation on an Unreal Engine project interactively,
+/// listing the operations recorded in the backups of the project with the
+/// most recent one first.
+pub fn undo_interactive() -> Result<(), String> {
+    let project_root = project_root_of(&get_project_root_from_user()?);
+    let Some(backup_root) = find_backup_root(&project_root, None)? else {
+        log::basic("No operations to undo.");
+        return Ok(());
+    };
+    let mut manifests = find_backup_sets(&backup_root)?;
+    if manifests.is_empty() {
+        log::basic("No operations to undo.");
+        return Ok(());
+    }
+    manifests.reverse();
+    let latest_id = manifests[0].id.clone();
+    let manifest = get_operation_from_user(manifests)?;
+    if manifest.id != latest_id {
+        log::warning(
+            "Operations after the chosen one may touch the same files and will not be undone.",
+        );
+    }
+    if !user_confirms_undo(&manifest)? {
+        return Ok(());
+    }
+    let manifest = undo(&backup_root, &manifest.id)?;
+    log::success(format!("Successfully undid {}.", manifest.operation));
+    Ok(())
+}
+
+/// Undo an operation by restoring its backup set, which reverts the renames
+/// of the operation and restores the files that it changed. The backup set
+/// is removed afterwards, so that the operation cannot be undone twice.
+pub fn undo(backup_root: &Path, id: &str) -> Result<Manifest, String> {
+    let backup_dir = find_backup_set(backup_root, id)?;
+    let manifest = restore_backup_set(&backup_dir)?;
+    print_restored_entries(&manifest);
+    fs::remove_dir_all(&backup_dir).map_err(|err| err.to_string())?;
+    Ok(manifest)
+}
+
+fn get_project_root_from_user() -> Result<PathBuf, String> {
+    get_project_path_from_user(
+        Text::new("Project root directory or .uproject file path:")
+            .with_validator(validate_project_root_is_dir)
+            .with_validator(validate_project_root_contains_project_descriptor),
+    )
+}
+
+fn validate_project_root_is_dir(project_root: &str) -> Result<Validation, CustomUserError> {
+    match project_root_of(Path::new(project_root)).is_dir() {
+        true => Ok(Validation::Valid),
+        false => {
+            let error_message = "Provided path is not a directory or a .uproject file";
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+fn validate_project_root_contains_project_descriptor(
+    project_root: &str,
+) -> Result<Validation, CustomUserError> {
+    match find_project_descriptors(&project_root_of(Path::new(project_root)))?.is_empty() {
+        false => Ok(Validation::Valid),
+        true => {
+            let error_message = "Provided directory does not contain a .uproject file";
+            Ok(Validation::Invalid(error_message.into()))
+        }
+    }
+}
+
+fn get_operation_from_user(manifests: Vec<Manifest>) -> Result<Manifest, String> {
+    let options = manifests
+        .into_iter()
+        .map(|manifest| SelectOption {
+            label: format!(
+                "{}  [{} ago, {} change(s)]",
+                manifest.operation,
+                format_age(Utc::now() - manifest.created_at),
+                manifest.entries.len()
+            ),
+            item: manifest,
+        })
+        .collect();
+    select_from_user("Choose an operation to undo:", options)
+}
+
+fn user_confirms_undo(manifest: &Manifest) -> Result<bool, String> {
+    Confirm::new(&format!("Undo {}?", manifest.operation))
+        .with_default(true)
+        .prompt()
+        .map_err(|err| err.to_string())
+}
@@ -5,6 +5,8 @@ pub enum Workflow {
     RenamePlugin,
     RenameTarget,
     RenameModule,
+    RenameContentFolder,
+    Undo,
 }
 
 impl Display for Workflow {
@@ -14,6 +16,8 @@ impl Display for Workflow {
             Workflow::RenamePlugin => write!(f, "Rename a plugin"),
             Workflow::RenameTarget => write!(f, "Rename a target"),
             Workflow::RenameModule => write!(f, "Rename a module"),
+            Workflow::RenameContentFolder => write!(f, "Rename a content folder"),
+            Workflow::Undo => write!(f, "Undo last rename"),
         }
     }
 }
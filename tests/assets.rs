@@ -0,0 +1,84 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use common::{package_with_names, stage_demo_project};
+use renom::{
+    package::{read_name_table, replace_names},
+    workflows::rename_module::{rename_module, Params},
+};
+
+fn names_in(path: &Path) -> Vec<String> {
+    read_name_table(&fs::read(path).unwrap())
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect()
+}
+
+#[test]
+fn replace_names_should_only_replace_names_of_same_length() {
+    let mut bytes = package_with_names(&["/Script/Demo", "Bücher", "None"]);
+
+    let (replaced, skipped) = replace_names(&mut bytes, |name| match name {
+        "/Script/Demo" => Some("/Script/Democracy".into()),
+        "Bücher" => Some("Bäcker".into()),
+        _ => None,
+    })
+    .unwrap();
+
+    assert_eq!(replaced, vec!["Bücher"]);
+    assert_eq!(skipped, vec!["/Script/Demo"]);
+    let names = read_name_table(&bytes)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["/Script/Demo", "Bäcker", "None"]);
+}
+
+#[test]
+fn rename_module_should_rewrite_asset_references_when_requested() {
+    let project_root = stage_demo_project("assets/rewrite_asset_references");
+    let map = project_root.join("Content/Maps/Main.umap");
+    let icon = project_root.join("Plugins/Tools/Content/Icon.uasset");
+    fs::create_dir_all(map.parent().unwrap()).unwrap();
+    fs::create_dir_all(icon.parent().unwrap()).unwrap();
+    fs::write(
+        &map,
+        package_with_names(&[
+            "/Script/Demo",
+            "/Script/Demo.DemoGameMode",
+            "/Script/DemoEditor",
+            "/Tools/Icon",
+            "None",
+        ]),
+    )
+    .unwrap();
+    fs::write(&icon, package_with_names(&["/Script/Demo", "None"])).unwrap();
+
+    let params = Params {
+        project_root: project_root.clone(),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: true,
+        backup_dir: Some(project_root.join(".renom/backup")),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+    rename_module(params).unwrap();
+
+    assert_eq!(
+        names_in(&map),
+        vec![
+            "/Script/Dome",
+            "/Script/Dome.DemoGameMode",
+            "/Script/DemoEditor",
+            "/Tools/Icon",
+            "None",
+        ]
+    );
+    assert_eq!(names_in(&icon), vec!["/Script/Dome", "None"]);
+}
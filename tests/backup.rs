@@ -0,0 +1,247 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{Duration, Utc};
+use renom::{
+    backup::{
+        assign_project_id, backup_path, backup_root, create_backup_set, find_backup_root,
+        find_backup_sets, project_id, read_manifest, restore_path, BackupKind, MANIFEST_FILE,
+        PROJECT_ID_FILE,
+    },
+    changes::{Change, RenameFile, ReplaceInFile},
+    engine::Engine,
+    workflows::{backup, backup::Params, parse_age, BackupAction},
+};
+
+fn staging_dir(name: &str) -> PathBuf {
+    let staging_dir = PathBuf::from("tests/temp/backup").join(name);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    fs::create_dir_all(staging_dir.join("backup")).unwrap();
+    staging_dir
+}
+
+#[test]
+fn backup_should_keep_binary_files_and_identical_files_apart() {
+    let staging_dir = staging_dir("binary_and_identical_files");
+    let backup_dir = staging_dir.join("backup");
+    let asset = staging_dir.join("Asset.uasset");
+    let first = staging_dir.join("First.ini");
+    let second = staging_dir.join("Second.ini");
+    fs::write(&asset, [0xC1, 0x83, 0x2A, 0x9E, 0xFF, 0x00]).unwrap();
+    fs::write(&first, "[URL]\nGameName=Demo\n").unwrap();
+    fs::write(&second, "[URL]\nGameName=Demo\n").unwrap();
+
+    let asset_blob = backup_path(&asset, &backup_dir).unwrap();
+    let first_blob = backup_path(&first, &backup_dir).unwrap();
+    let second_blob = backup_path(&second, &backup_dir).unwrap();
+    fs::write(&asset, "overwritten").unwrap();
+    fs::write(&first, "overwritten").unwrap();
+
+    assert_ne!(first_blob, second_blob);
+    let manifest = read_manifest(&backup_dir).unwrap();
+    let originals = manifest
+        .entries
+        .iter()
+        .map(|entry| (entry.original.clone(), entry.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        originals,
+        vec![
+            (asset.clone(), BackupKind::File),
+            (first.clone(), BackupKind::File),
+            (second.clone(), BackupKind::File),
+        ]
+    );
+
+    restore_path(&asset_blob, &asset).unwrap();
+    restore_path(&first_blob, &first).unwrap();
+    assert_eq!(
+        fs::read(&asset).unwrap(),
+        [0xC1, 0x83, 0x2A, 0x9E, 0xFF, 0x00]
+    );
+    assert_eq!(
+        fs::read_to_string(&first).unwrap(),
+        "[URL]\nGameName=Demo\n"
+    );
+}
+
+#[test]
+fn backup_should_restore_whole_directories() {
+    let staging_dir = staging_dir("whole_directories");
+    let backup_dir = staging_dir.join("backup");
+    let plugin_root = staging_dir.join("Plugins/Tools");
+    fs::create_dir_all(plugin_root.join("Content")).unwrap();
+    fs::write(plugin_root.join("Tools.uplugin"), "{}").unwrap();
+    fs::write(plugin_root.join("Content/Icon.uasset"), [0xC1, 0x83]).unwrap();
+
+    let blob = backup_path(&plugin_root, &backup_dir).unwrap();
+    fs::remove_dir_all(&plugin_root).unwrap();
+    restore_path(&blob, &plugin_root).unwrap();
+
+    let manifest = read_manifest(&backup_dir).unwrap();
+    assert_eq!(manifest.entries[0].kind, BackupKind::Dir);
+    assert_eq!(
+        fs::read_to_string(plugin_root.join("Tools.uplugin")).unwrap(),
+        "{}"
+    );
+    assert_eq!(
+        fs::read(plugin_root.join("Content/Icon.uasset")).unwrap(),
+        [0xC1, 0x83]
+    );
+}
+
+#[test]
+fn backup_sets_should_be_restored_and_pruned_per_operation() {
+    let project_root = staging_dir("backup_sets");
+    let config = project_root.join("Config/DefaultEngine.ini");
+    fs::create_dir_all(config.parent().unwrap()).unwrap();
+    fs::write(&config, "[URL]\nGameName=Demo\n").unwrap();
+
+    let backup_dir = project_root.join("backup");
+    let backup_root = backup_root(&project_root, Some(&backup_dir)).unwrap();
+    let old_set = create_backup_set(&backup_root, "rename project Demo to Dome").unwrap();
+    backup_path(&config, &old_set).unwrap();
+    fs::write(&config, "[URL]\nGameName=Dome\n").unwrap();
+    let new_set = create_backup_set(&backup_root, "rename project Dome to Home").unwrap();
+    backup_path(&config, &new_set).unwrap();
+    fs::write(&config, "[URL]\nGameName=Home\n").unwrap();
+
+    let old_id = old_set.file_name().unwrap().to_str().unwrap().to_owned();
+    let escaping_id = format!("../backup/{}", old_id);
+    assert!(backup(Params {
+        project_root: project_root.clone(),
+        backup_dir: Some(backup_dir.clone()),
+        action: BackupAction::Restore { id: escaping_id },
+    })
+    .is_err());
+    backup(Params {
+        project_root: project_root.clone(),
+        backup_dir: Some(backup_dir.clone()),
+        action: BackupAction::Restore { id: old_id },
+    })
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "[URL]\nGameName=Demo\n"
+    );
+
+    let mut manifest = read_manifest(&old_set).unwrap();
+    manifest.created_at = Utc::now() - Duration::days(40);
+    fs::write(
+        old_set.join(MANIFEST_FILE),
+        serde_json::to_string(&manifest).unwrap(),
+    )
+    .unwrap();
+    backup(Params {
+        project_root: project_root.clone(),
+        backup_dir: Some(backup_dir.clone()),
+        action: BackupAction::Prune {
+            older_than: parse_age("30d").unwrap(),
+        },
+    })
+    .unwrap();
+
+    let remaining = find_backup_sets(&backup_root).unwrap();
+    assert!(!old_set.exists());
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].operation, "rename project Dome to Home");
+}
+
+#[test]
+fn parse_age_should_reject_unknown_units() {
+    assert_eq!(parse_age("12h"), Ok(Duration::hours(12)));
+    assert!(parse_age("12y").is_err());
+    assert!(parse_age("").is_err());
+    assert!(parse_age("30é").is_err());
+    assert!(parse_age("-5d").is_err());
+    assert!(parse_age("0d").is_err());
+}
+
+#[test]
+fn backup_root_should_default_to_a_location_keyed_by_project_id() {
+    let project_root = staging_dir("default_location");
+
+    let root = backup_root(&project_root, None).unwrap();
+
+    let id = project_id(&project_root).unwrap().unwrap();
+    assert!(root.is_absolute());
+    assert!(root.ends_with(&id));
+    assert!(!root.starts_with(fs::canonicalize(&project_root).unwrap()));
+    assert_eq!(root, backup_root(&project_root, None).unwrap());
+    assert_eq!(find_backup_root(&project_root, None).unwrap(), Some(root));
+}
+
+#[test]
+fn listing_backups_should_not_assign_a_project_id() {
+    let project_root = staging_dir("list_without_id");
+
+    backup(Params {
+        project_root: project_root.clone(),
+        backup_dir: None,
+        action: BackupAction::List,
+    })
+    .unwrap();
+
+    assert!(!project_root.join(PROJECT_ID_FILE).exists());
+    assert_eq!(find_backup_root(&project_root, None).unwrap(), None);
+}
+
+#[test]
+fn copied_projects_should_not_share_a_project_id() {
+    let original_root = staging_dir("copied_project");
+    let id = assign_project_id(&original_root).unwrap();
+    let copied_root = original_root.with_file_name("copied_project_copy");
+    if copied_root.is_dir() {
+        fs::remove_dir_all(&copied_root).unwrap();
+    }
+    fs::create_dir_all(copied_root.join(".renom")).unwrap();
+    fs::copy(
+        original_root.join(PROJECT_ID_FILE),
+        copied_root.join(PROJECT_ID_FILE),
+    )
+    .unwrap();
+
+    assert_eq!(project_id(&copied_root).unwrap(), None);
+    let copied_id = assign_project_id(&copied_root).unwrap();
+    assert_ne!(copied_id, id);
+    assert_eq!(project_id(&original_root).unwrap(), Some(id.clone()));
+
+    let moved_root = original_root.with_file_name("copied_project_moved");
+    if moved_root.is_dir() {
+        fs::remove_dir_all(&moved_root).unwrap();
+    }
+    fs::rename(&original_root, &moved_root).unwrap();
+    assert_eq!(project_id(&moved_root).unwrap(), Some(id));
+}
+
+#[test]
+fn revert_should_find_backups_inside_renamed_directories() {
+    let staging_dir = staging_dir("renamed_directories");
+    let project_root = staging_dir.join("Demo");
+    let renamed_root = staging_dir.join("Dome");
+    let config = project_root.join("Config/DefaultEngine.ini");
+    fs::create_dir_all(config.parent().unwrap()).unwrap();
+    fs::write(&config, "[URL]\nGameName=Demo\n").unwrap();
+    let root = backup_root(&project_root, Some(&project_root.join(".renom/backup"))).unwrap();
+    let backup_dir = create_backup_set(&root, "rename project Demo to Dome").unwrap();
+
+    let changeset = vec![
+        Change::ReplaceInFile(ReplaceInFile::new(&config, "Demo", "Dome")),
+        Change::RenameFile(RenameFile::new(&project_root, &renamed_root)),
+        Change::ReplaceInFile(ReplaceInFile::new(
+            renamed_root.join("Missing.ini"),
+            "Demo",
+            "Dome",
+        )),
+    ];
+    let mut engine = Engine::new();
+    assert!(engine.execute(changeset, backup_dir).is_err());
+    engine.revert().unwrap();
+
+    assert!(!renamed_root.exists());
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "[URL]\nGameName=Demo\n"
+    );
+}
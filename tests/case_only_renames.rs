@@ -0,0 +1,80 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use common::stage_demo_project;
+use renom::{
+    changes::{Change, RenameFile},
+    engine::Engine,
+    workflows::rename_target::{rename_target, Params},
+};
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut names = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn rename_target_should_change_only_the_case_of_a_name() {
+    let project_root = stage_demo_project("case_only_renames/rename_target");
+
+    rename_target(Params {
+        project_root: project_root.clone(),
+        target: "DemoEditor".into(),
+        new_name: "DEMOEditor".into(),
+        backup_dir: Some(project_root.join(".renom/backup")),
+        vcs: Default::default(),
+        engine: Default::default(),
+        clean: false,
+    })
+    .unwrap();
+
+    let source_files = file_names(&project_root.join("Source"));
+    assert!(source_files.contains(&"DEMOEditor.Target.cs".to_owned()));
+    assert!(!source_files.contains(&"DemoEditor.Target.cs".to_owned()));
+    assert!(!source_files.iter().any(|name| name.ends_with(".renom-tmp")));
+}
+
+#[test]
+fn rename_target_should_reject_names_that_only_differ_in_case_from_another() {
+    let project_root = stage_demo_project("case_only_renames/reject_collision");
+
+    let result = rename_target(Params {
+        project_root: project_root.clone(),
+        target: "DemoEditor".into(),
+        new_name: "DEMO".into(),
+        backup_dir: Some(project_root.join(".renom/backup")),
+        vcs: Default::default(),
+        engine: Default::default(),
+        clean: false,
+    });
+
+    assert_eq!(
+        result.err(),
+        Some("new name must not conflict with the existing target 'Demo'".into())
+    );
+}
+
+#[test]
+fn engine_should_revert_case_only_renames() {
+    let project_root = stage_demo_project("case_only_renames/revert");
+    let from = project_root.join("Source/Demo");
+    let to = project_root.join("Source/demo");
+    let changeset = vec![Change::RenameFile(RenameFile::new(&from, &to))];
+
+    let mut engine = Engine::new();
+    engine
+        .execute(changeset, project_root.join(".renom/backup"))
+        .unwrap();
+    assert!(file_names(&project_root.join("Source")).contains(&"demo".to_owned()));
+    engine.revert().unwrap();
+
+    let source_files = file_names(&project_root.join("Source"));
+    assert!(source_files.contains(&"Demo".to_owned()));
+    assert!(!source_files.contains(&"demo".to_owned()));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{stage_demo_project, write};
+use renom::workflows::{
+    clean::{clean, Params},
+    rename_plugin,
+};
+
+/// Stage the demo project along with generated files for the project and
+/// its plugins.
+fn stage(name: &str) -> PathBuf {
+    let project_root = stage_demo_project(&format!("clean/{}", name));
+    write(&project_root.join("Binaries/Win64/Demo.dll"), "");
+    write(&project_root.join("Intermediate/Build/Makefile.bin"), "");
+    write(&project_root.join("Demo.sln"), "");
+    write(
+        &project_root.join("Plugins/Tools/Binaries/Win64/Tools.dll"),
+        "",
+    );
+    write(
+        &project_root.join("Plugins/Tools/Plugins/Nested/Intermediate/Build/Nested.obj"),
+        "",
+    );
+    project_root
+}
+
+#[test]
+fn clean_should_remove_generated_files_without_backups() {
+    let project_root = stage("remove_generated_files");
+
+    clean(Params {
+        project_root: project_root.clone(),
+    })
+    .unwrap();
+
+    assert!(!project_root.join("Binaries").exists());
+    assert!(!project_root.join("Intermediate").exists());
+    assert!(!project_root.join("Demo.sln").exists());
+    assert!(!project_root.join("Plugins/Tools/Binaries").exists());
+    assert!(!project_root
+        .join("Plugins/Tools/Plugins/Nested/Intermediate")
+        .exists());
+    assert!(project_root.join("Demo.uproject").is_file());
+    assert!(!project_root.join(".renom").exists());
+}
+
+#[test]
+fn rename_plugin_should_clean_generated_files_of_renamed_plugin() {
+    let project_root = stage("rename_plugin");
+
+    rename_plugin::rename_plugin(rename_plugin::Params {
+        project_root: project_root.clone(),
+        plugin: "Tools".into(),
+        new_name: "Gear".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.with_file_name("backup")),
+        clean: true,
+        vcs: Default::default(),
+        engine: Default::default(),
+    })
+    .unwrap();
+
+    let plugin_root = project_root.join("Plugins/Gear");
+    assert!(plugin_root.join("Gear.uplugin").is_file());
+    assert!(!plugin_root.join("Binaries").exists());
+    assert!(!plugin_root.join("Plugins/Nested/Intermediate").exists());
+    assert!(!project_root.join("Binaries").exists());
+}
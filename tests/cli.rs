@@ -0,0 +1,81 @@
+use std::{convert::TryFrom, path::PathBuf};
+
+use clap::Parser;
+use renom::{
+    cli::{Cli, Command},
+    workflows::{rename_module, rename_project},
+};
+
+fn parse(args: &[&str]) -> Command {
+    let args = std::iter::once("renom").chain(args.iter().copied());
+    Cli::try_parse_from(args).unwrap().command.unwrap()
+}
+
+#[test]
+fn rename_module_should_take_every_argument_from_command_line_without_prompting() {
+    let command = parse(&[
+        "rename-module",
+        "--project",
+        "tests/resources/project/Demo",
+        "--module",
+        "Demo",
+        "--new-name",
+        "Dome",
+        "--no-input",
+        "--clean",
+    ]);
+    let params = match command {
+        Command::RenameModule(args) => args
+            .resolve_missing_args()
+            .and_then(rename_module::Params::try_from)
+            .unwrap(),
+        _ => panic!("expected rename-module"),
+    };
+
+    assert_eq!(
+        params.project_root,
+        PathBuf::from("tests/resources/project/Demo")
+    );
+    assert_eq!(params.module, "Demo");
+    assert_eq!(params.new_name, "Dome");
+    assert!(params.clean);
+}
+
+#[test]
+fn rename_module_should_fail_for_missing_arguments_with_no_input() {
+    let command = parse(&[
+        "rename-module",
+        "--project",
+        "tests/resources/project/Demo",
+        "--new-name",
+        "Dome",
+        "--no-input",
+    ]);
+    let result = match command {
+        Command::RenameModule(args) => args
+            .resolve_missing_args()
+            .and_then(rename_module::Params::try_from),
+        _ => panic!("expected rename-module"),
+    };
+
+    assert_eq!(
+        result.err(),
+        Some("--module is required with --no-input".into())
+    );
+}
+
+#[test]
+fn rename_project_should_fail_for_missing_project_with_no_input() {
+    let command = parse(&["rename-project", "--new-name", "Dome", "--no-input"]);
+    let result = match command {
+        Command::RenameProject(args) => args
+            .resolve_missing_args()
+            .and_then(rename_project::Params::try_from),
+        _ => panic!("expected rename-project"),
+    };
+
+    assert_eq!(
+        result.err(),
+        Some("--project is required with --no-input".into())
+    );
+}
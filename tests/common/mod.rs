@@ -0,0 +1,85 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+/// Copy a directory with all of its contents.
+pub fn copy_dir(from: &Path, to: &Path) {
+    for entry in WalkDir::new(from).into_iter().filter_map(Result::ok) {
+        let target = to.join(entry.path().strip_prefix(from).unwrap());
+        if entry.path().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+        } else {
+            fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+}
+
+/// Write a file, creating its directory if needed.
+pub fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Write a binary file, creating its directory if needed.
+pub fn write_bytes(path: &Path, content: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Stage a fresh copy of the demo project in `tests/temp/<staging_dir>/Demo`
+/// and return its root. Backups and other files can be staged next to it.
+pub fn stage_demo_project(staging_dir: &str) -> PathBuf {
+    let staging_dir = PathBuf::from("tests/temp").join(staging_dir);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    let project_root = staging_dir.join("Demo");
+    copy_dir(Path::new("tests/resources/project/Demo"), &project_root);
+    project_root
+}
+
+/// Write a string as serialized in packages, with a length prefix and a
+/// null terminator.
+fn write_string(bytes: &mut Vec<u8>, string: &str) {
+    match string.is_ascii() {
+        true => {
+            bytes.extend((string.len() as i32 + 1).to_le_bytes());
+            bytes.extend(string.as_bytes());
+            bytes.push(0);
+        }
+        false => {
+            let units = string.encode_utf16().collect::<Vec<_>>();
+            bytes.extend((-(units.len() as i32) - 1).to_le_bytes());
+            units
+                .iter()
+                .for_each(|unit| bytes.extend(unit.to_le_bytes()));
+            bytes.extend([0, 0]);
+        }
+    }
+}
+
+/// Build a minimal package with a UE 4.27 summary followed by a name table.
+pub fn package_with_names(names: &[&str]) -> Vec<u8> {
+    let mut bytes = vec![];
+    bytes.extend(0x9E2A83C1u32.to_le_bytes());
+    for value in [-7i32, 864, 522, 0, 0, 0] {
+        bytes.extend(value.to_le_bytes());
+    }
+    write_string(&mut bytes, "None");
+    bytes.extend(0u32.to_le_bytes());
+    bytes.extend((names.len() as i32).to_le_bytes());
+    let name_offset = bytes.len() as i32 + 4;
+    bytes.extend(name_offset.to_le_bytes());
+    for name in names {
+        write_string(&mut bytes, name);
+        bytes.extend([0, 0, 0, 0]);
+    }
+    bytes
+}
@@ -0,0 +1,58 @@
+use std::{fs, path::PathBuf};
+
+use renom::config::{Config, MAX_RECENT_PROJECTS};
+
+fn staging_dir(name: &str) -> PathBuf {
+    let staging_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/temp/config")
+        .join(name);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    staging_dir
+}
+
+#[test]
+fn add_recent_project_should_move_project_to_front_without_duplicates() {
+    let mut config = Config::default();
+    let first = PathBuf::from("/projects/First");
+    let second = PathBuf::from("/projects/Second");
+
+    config.add_recent_project(&first).unwrap();
+    config.add_recent_project(&second).unwrap();
+    config.add_recent_project(&first).unwrap();
+
+    assert_eq!(config.recent_projects, vec![first, second]);
+}
+
+#[test]
+fn add_recent_project_should_forget_least_recent_projects() {
+    let mut config = Config::default();
+
+    for index in 0..=MAX_RECENT_PROJECTS {
+        config
+            .add_recent_project(&PathBuf::from(format!("/projects/{}", index)))
+            .unwrap();
+    }
+
+    assert_eq!(config.recent_projects.len(), MAX_RECENT_PROJECTS);
+    assert!(!config
+        .recent_projects
+        .contains(&PathBuf::from("/projects/0")));
+}
+
+#[test]
+fn config_should_survive_save_and_load() {
+    let config_path = staging_dir("save_and_load").join("renom/config.toml");
+    let project = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/resources/project/Demo");
+    let mut config = Config::load(&config_path).unwrap();
+    assert_eq!(config, Config::default());
+
+    config.add_recent_project(&project).unwrap();
+    config.add_recent_project(&project.join("Missing")).unwrap();
+    config.save(&config_path).unwrap();
+    let config = Config::load(&config_path).unwrap();
+
+    assert_eq!(config.recent_projects.len(), 2);
+    assert_eq!(config.existing_recent_projects(), vec![project]);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::fs;
+
+use common::{stage_demo_project, write};
+use renom::workflows::{rename_module, rename_plugin};
+
+#[test]
+fn rename_module_should_update_class_paths_in_all_config_files() {
+    let project_root = stage_demo_project("config_references/rename_module");
+    write(
+        &project_root.join("Config/Windows/WindowsEngine.ini"),
+        "[/Script/Engine.Engine]\nGameViewportClientClassName=/Script/Demo.DemoViewport\n",
+    );
+    write(
+        &project_root.join("Plugins/Tools/Config/DefaultTools.ini"),
+        "[/Script/ToolsRuntime.ToolsSettings]\nGameInstance=Class'/Script/Demo.DemoGameInstance'\nGameInstanceClassName=\"/Script/Demo.DemoGameInstance\"\nPackage=/Script/Demo\nEditor=/Script/DemoEditor\n+ClassRedirects=(OldName=\"/Script/Demo.OldGameInstance\",NewName=\"/Script/Demo.DemoGameInstance\")\n",
+    );
+
+    let params = rename_module::Params {
+        project_root: project_root.clone(),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.join(".renom/backup")),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+    rename_module::rename_module(params).unwrap();
+
+    let engine_config = fs::read_to_string(project_root.join("Config/DefaultEngine.ini")).unwrap();
+    assert!(engine_config.contains("GlobalDefaultGameMode=/Script/Dome.DemoGameMode"));
+    assert!(engine_config.contains(r#"NewName="/Script/Dome")"#));
+    assert!(!engine_config.contains(r#"NewName="/Script/Demo")"#));
+    assert!(engine_config
+        .contains(r#"+PackageRedirects=(OldName="/Script/Demo",NewName="/Script/Dome")"#));
+    let platform_config =
+        fs::read_to_string(project_root.join("Config/Windows/WindowsEngine.ini")).unwrap();
+    assert!(platform_config.contains("GameViewportClientClassName=/Script/Dome.DemoViewport"));
+    let plugin_config =
+        fs::read_to_string(project_root.join("Plugins/Tools/Config/DefaultTools.ini")).unwrap();
+    assert!(plugin_config.contains("GameInstance=Class'/Script/Dome.DemoGameInstance'"));
+    assert!(plugin_config.contains(r#"GameInstanceClassName="/Script/Dome.DemoGameInstance""#));
+    assert!(plugin_config.contains("Package=/Script/Dome\n"));
+    assert!(plugin_config.contains("Editor=/Script/DemoEditor\n"));
+    assert!(plugin_config.contains(
+        r#"+ClassRedirects=(OldName="/Script/Demo.OldGameInstance",NewName="/Script/Dome.DemoGameInstance")"#
+    ));
+}
+
+#[test]
+fn rename_plugin_should_update_content_paths_in_all_config_files() {
+    let project_root = stage_demo_project("config_references/rename_plugin");
+    let engine_config_file = project_root.join("Config/DefaultEngine.ini");
+    let engine_config = fs::read_to_string(&engine_config_file).unwrap().replace(
+        "GameDefaultMap=/Game/Maps/Entry.Entry",
+        "GameDefaultMap=/Nested/Maps/Entry.Entry",
+    );
+    fs::write(&engine_config_file, engine_config).unwrap();
+    write(
+        &project_root.join("Plugins/Tools/Plugins/Nested/Config/DefaultNested.ini"),
+        "[/Script/NestedCore.NestedSettings]\nStartupMap=/Nested/Maps/Start.Start\n",
+    );
+
+    let params = rename_plugin::Params {
+        project_root: project_root.clone(),
+        plugin: "Nested".into(),
+        new_name: "Kit".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.join(".renom/backup")),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+    rename_plugin::rename_plugin(params).unwrap();
+
+    let engine_config = fs::read_to_string(&engine_config_file).unwrap();
+    assert!(engine_config.contains("GameDefaultMap=/Kit/Maps/Entry.Entry"));
+    assert!(engine_config
+        .contains(r#"+PackageRedirects=(OldName="/Nested/",NewName="/Kit/",MatchSubstring=true)"#));
+    let plugin_config =
+        fs::read_to_string(project_root.join("Plugins/Tools/Plugins/Kit/Config/DefaultNested.ini"))
+            .unwrap();
+    assert!(plugin_config.contains("StartupMap=/Kit/Maps/Start.Start"));
+}
+
+#[test]
+fn rename_plugin_should_follow_nested_plugins_into_renamed_root() {
+    let project_root = stage_demo_project("config_references/rename_parent_plugin");
+    write(
+        &project_root.join("Plugins/Tools/Plugins/Nested/Config/DefaultNested.ini"),
+        "[/Script/NestedCore.NestedSettings]\nStartupMap=/Tools/Maps/Start.Start\nWidget=/Game/Tools/Widget.Widget\n",
+    );
+    let nested_descriptor = project_root.join("Plugins/Tools/Plugins/Nested/Nested.uplugin");
+    let descriptor = fs::read_to_string(&nested_descriptor).unwrap().replace(
+        r#""Modules": ["#,
+        r#""Plugins": [{ "Name": "Tools", "Enabled": true }],
+	"Modules": ["#,
+    );
+    fs::write(&nested_descriptor, descriptor).unwrap();
+
+    let params = rename_plugin::Params {
+        project_root: project_root.clone(),
+        plugin: "Tools".into(),
+        new_name: "Gear".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.join(".renom/backup")),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+    rename_plugin::rename_plugin(params).unwrap();
+
+    let nested_root = project_root.join("Plugins/Gear/Plugins/Nested");
+    assert!(project_root.join("Plugins/Gear/Gear.uplugin").is_file());
+    assert!(!project_root.join("Plugins/Tools").exists());
+    let descriptor = fs::read_to_string(nested_root.join("Nested.uplugin")).unwrap();
+    assert!(descriptor.contains(r#""Name": "Gear""#));
+    let plugin_config = fs::read_to_string(nested_root.join("Config/DefaultNested.ini")).unwrap();
+    assert!(plugin_config.contains("StartupMap=/Gear/Maps/Start.Start"));
+    assert!(plugin_config.contains("Widget=/Game/Tools/Widget.Widget"));
+}
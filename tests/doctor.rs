@@ -0,0 +1,72 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use common::stage_demo_project;
+use renom::workflows::doctor::{diagnose, doctor, gather_context, Params};
+
+fn replace_in(path: &Path, from: &str, to: &str) {
+    let content = fs::read_to_string(path).unwrap();
+    fs::write(path, content.replace(from, to)).unwrap();
+}
+
+#[test]
+fn doctor_should_detect_and_fix_inconsistencies() {
+    let project_root = stage_demo_project("doctor/detect_and_fix_inconsistencies");
+
+    let module_root = project_root.join("Source/Demo");
+    replace_in(&module_root.join("Demo.Build.cs"), "Demo", "Prototype");
+    replace_in(
+        &module_root.join("Demo.Build.cs"),
+        r#""ToolsRuntime""#,
+        r#""ToolsRuntime", "Prototype""#,
+    );
+    replace_in(
+        &module_root.join("Public/DemoGameMode.h"),
+        "DEMO_API",
+        "PROTOTYPE_API",
+    );
+    replace_in(
+        &module_root.join("Private/Demo.cpp"),
+        "FDefaultGameModuleImpl, Demo,",
+        "FDefaultGameModuleImpl, Prototype,",
+    );
+    replace_in(
+        &project_root.join("Demo.uproject"),
+        r#""Name": "DemoEditor""#,
+        r#""Name": "Missing""#,
+    );
+    replace_in(
+        &project_root.join("Config/DefaultEngine.ini"),
+        r#"NewName="/Script/Demo")"#,
+        r#"NewName="/Script/Demo")
++PackageRedirects=(OldName="/Script/Old",NewName="/Script/Gone")"#,
+    );
+
+    let params = Params {
+        project_root: project_root.clone(),
+        fix: true,
+        backup_dir: Some(project_root.join(".renom/backup")),
+    };
+    let issues = diagnose(&gather_context(&params).unwrap()).unwrap();
+    assert_eq!(issues.len(), 5);
+    assert_eq!(issues.iter().filter(|issue| issue.fix.is_none()).count(), 1);
+
+    doctor(params).unwrap();
+
+    let params = Params {
+        project_root: project_root.clone(),
+        fix: false,
+        backup_dir: None,
+    };
+    let remaining = diagnose(&gather_context(&params).unwrap()).unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining[0].description.contains("Missing"));
+    let build_file = fs::read_to_string(module_root.join("Demo.Build.cs")).unwrap();
+    assert!(build_file.contains("public class Demo : ModuleRules"));
+    assert!(build_file.contains("public Demo(ReadOnlyTargetRules Target)"));
+    assert!(build_file.contains(r#""ToolsRuntime", "Prototype""#));
+    let config = fs::read_to_string(project_root.join("Config/DefaultEngine.ini")).unwrap();
+    assert!(!config.contains("/Script/Gone"));
+    assert!(config.contains(r#"NewName="/Script/Demo""#));
+}
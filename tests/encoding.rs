@@ -0,0 +1,128 @@
+use std::{fs, path::PathBuf};
+
+use renom::{
+    changes::{AppendIniEntry, Change, ReplaceInFile, SetIniEntry},
+    encoding::{decode, decode_text, encode, read_text, read_to_string, Encoding, LineEnding},
+};
+
+fn stage(resource: &str, name: &str) -> (PathBuf, PathBuf) {
+    let original = PathBuf::from("tests/resources/ini").join(resource);
+    let staging_dir = PathBuf::from("tests/temp/encoding").join(name);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    fs::create_dir_all(&staging_dir).unwrap();
+    let file = staging_dir.join(resource);
+    fs::copy(&original, &file).unwrap();
+    (staging_dir, file)
+}
+
+#[test]
+fn ini_set_should_preserve_utf16_encoding_and_crlf_line_endings() {
+    let (staging_dir, config) = stage("utf16le_crlf.ini", "ini_set_should_preserve_utf16");
+
+    let change = Change::SetIniEntry(SetIniEntry::new(&config, "URL", "GameName", "Dämo"));
+    let _revert = change.apply(&staging_dir).unwrap();
+
+    let file = read_text(&config).unwrap();
+    assert_eq!(file.encoding, Encoding::Utf16Le);
+    assert_eq!(file.line_ending, LineEnding::CrLf);
+    assert!(!file.content.replace("\r\n", "").contains('\n'));
+    assert!(file.content.contains("GameName=Dämo\r\n"));
+    assert!(file.content.contains("ProjectName=Démo\r\n"));
+}
+
+#[test]
+fn ini_append_should_preserve_crlf_line_endings() {
+    let (staging_dir, config) = stage("crlf.ini", "ini_append_should_preserve_crlf");
+
+    let change = Change::AppendIniEntry(AppendIniEntry::new(
+        &config,
+        "CoreRedirects",
+        "+PackageRedirects",
+        r#"(OldName="/Script/Old",NewName="/Script/New")"#,
+    ));
+    let _revert = change.apply(&staging_dir).unwrap();
+
+    let file = read_text(&config).unwrap();
+    assert_eq!(file.encoding, Encoding::Utf8);
+    assert_eq!(file.line_ending, LineEnding::CrLf);
+    assert!(!file.content.replace("\r\n", "").contains('\n'));
+    assert!(file.content.contains("[CoreRedirects]\r\n"));
+}
+
+#[test]
+fn replace_in_file_should_preserve_utf16_encoding_and_revert_to_original_bytes() {
+    let (staging_dir, config) = stage("utf16le_crlf.ini", "replace_should_preserve_utf16");
+    let original_bytes = fs::read(&config).unwrap();
+
+    let change = Change::ReplaceInFile(ReplaceInFile::new(&config, "Demo", "Dome"));
+    let revert = change.apply(&staging_dir).unwrap();
+
+    let file = read_text(&config).unwrap();
+    assert_eq!(file.encoding, Encoding::Utf16Le);
+    assert!(file.content.contains("GameName=Dome\r\n"));
+    assert!(file.content.contains("Überprüfung"));
+
+    revert(&staging_dir).unwrap();
+    assert_eq!(fs::read(&config).unwrap(), original_bytes);
+}
+
+#[test]
+fn decode_should_detect_utf16_without_byte_order_mark() {
+    let little_endian = "[URL]\r\nGameName=Démo\r\n"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    let big_endian = "[URL]\r\nGameName=Démo\r\n"
+        .encode_utf16()
+        .flat_map(u16::to_be_bytes)
+        .collect::<Vec<_>>();
+
+    let (content, encoding) = decode_text(&little_endian).unwrap();
+    assert_eq!(encoding, Encoding::Utf16LeNoBom);
+    assert_eq!(content, "[URL]\r\nGameName=Démo\r\n");
+    assert_eq!(encode(&content, encoding).unwrap(), little_endian);
+    let (content, encoding) = decode_text(&big_endian).unwrap();
+    assert_eq!(encoding, Encoding::Utf16BeNoBom);
+    assert_eq!(encode(&content, encoding).unwrap(), big_endian);
+}
+
+#[test]
+fn replace_in_file_should_preserve_latin1_encoding() {
+    let staging_dir = PathBuf::from("tests/temp/encoding/replace_should_preserve_latin1");
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    fs::create_dir_all(&staging_dir).unwrap();
+    let config = staging_dir.join("latin1.ini");
+    fs::write(&config, b"[URL]\nGameName=Demo\nProjectName=D\xe9mo\n").unwrap();
+
+    let change = Change::ReplaceInFile(ReplaceInFile::new(&config, "Demo", "Dome"));
+    let _revert = change.apply(&staging_dir).unwrap();
+
+    assert_eq!(read_text(&config).unwrap().encoding, Encoding::Latin1);
+    assert_eq!(
+        fs::read(&config).unwrap(),
+        b"[URL]\nGameName=Dome\nProjectName=D\xe9mo\n"
+    );
+    assert!(encode("Dömo ✓", Encoding::Latin1).is_err());
+}
+
+#[test]
+fn decode_should_fail_for_binary_packages() {
+    let staging_dir = PathBuf::from("tests/temp/encoding/decode_should_fail_for_binary");
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    fs::create_dir_all(&staging_dir).unwrap();
+    // The magic number of a package followed by a name table entry.
+    let mut package = vec![0xC1, 0x83, 0x2A, 0x9E, 0xF9, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0];
+    package.extend_from_slice(b"/Script/Demo\0");
+    package.resize(64, 0);
+    let asset = staging_dir.join("A.uasset");
+    fs::write(&asset, &package).unwrap();
+
+    assert!(decode(&package).is_err());
+    assert!(read_to_string(&asset).is_err());
+}
@@ -0,0 +1,109 @@
+use std::{fs, path::PathBuf};
+
+use renom::workflows::{build_graph, Edge, NodeKind};
+
+#[test]
+fn graph_should_classify_modules_and_dependencies() {
+    let project_root = PathBuf::from("tests/resources/project/Demo");
+
+    let graph = build_graph(&project_root).unwrap();
+
+    let kind_of = |name: &str| {
+        graph
+            .nodes
+            .iter()
+            .find(|node| node.name == name)
+            .map(|node| node.kind)
+    };
+    assert_eq!(kind_of("Demo"), Some(NodeKind::Project));
+    assert_eq!(kind_of("ToolsRuntime"), Some(NodeKind::Plugin));
+    assert_eq!(kind_of("Core"), Some(NodeKind::External));
+
+    assert!(graph.edges.contains(&Edge {
+        from: "Demo".into(),
+        to: "ToolsRuntime".into(),
+        public: false,
+    }));
+    assert!(graph.edges.contains(&Edge {
+        from: "DemoEditor".into(),
+        to: "Demo".into(),
+        public: false,
+    }));
+    assert!(graph.plugin_edges.contains(&Edge {
+        from: "Tools".into(),
+        to: "Nested".into(),
+        public: true,
+    }));
+    assert!(graph.cycles.is_empty());
+    assert!(graph.violations.is_empty());
+}
+
+#[test]
+fn graph_should_list_modules_of_nested_plugins_once() {
+    let project_root = PathBuf::from("tests/resources/project/Demo");
+
+    let graph = build_graph(&project_root).unwrap();
+
+    let nested_core_edges = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.from == "NestedCore")
+        .collect::<Vec<_>>();
+    assert_eq!(
+        nested_core_edges,
+        vec![&Edge {
+            from: "NestedCore".into(),
+            to: "Core".into(),
+            public: true,
+        }]
+    );
+    let nested_core = graph
+        .nodes
+        .iter()
+        .find(|node| node.name == "NestedCore")
+        .unwrap();
+    assert_eq!(nested_core.plugin.as_deref(), Some("Nested"));
+    for (index, edge) in graph.edges.iter().enumerate() {
+        assert!(!graph.edges[..index].contains(edge), "{:?}", edge);
+    }
+}
+
+#[test]
+fn graph_should_flag_cycles_and_violations() {
+    let staging_dir = PathBuf::from("tests/temp/graph/flag_cycles_and_violations");
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    let module_root = staging_dir.join("Plugins/Tools/Source/ToolsRuntime");
+    fs::create_dir_all(staging_dir.join("Source/Demo")).unwrap();
+    fs::create_dir_all(&module_root).unwrap();
+    fs::write(staging_dir.join("Demo.uproject"), "{}").unwrap();
+    fs::write(staging_dir.join("Plugins/Tools/Tools.uplugin"), "{}").unwrap();
+    fs::write(
+        staging_dir.join("Source/Demo/Demo.Build.cs"),
+        r#"PrivateDependencyModuleNames.AddRange(new string[] { "ToolsRuntime" });"#,
+    )
+    .unwrap();
+    fs::write(
+        module_root.join("ToolsRuntime.Build.cs"),
+        r#"PublicDependencyModuleNames.Add("Demo");
+        // PublicDependencyModuleNames.Add("Commented");"#,
+    )
+    .unwrap();
+
+    let graph = build_graph(&staging_dir).unwrap();
+
+    assert_eq!(
+        graph.cycles,
+        vec![vec!["Demo".to_owned(), "ToolsRuntime".to_owned()]]
+    );
+    assert_eq!(
+        graph.violations,
+        vec![Edge {
+            from: "ToolsRuntime".into(),
+            to: "Demo".into(),
+            public: true,
+        }]
+    );
+    assert!(graph.nodes.iter().all(|node| node.name != "Commented"));
+}
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use renom::workflows::gather_report;
+
+#[test]
+fn inspect_should_report_project_structure() {
+    let project_root = PathBuf::from("tests/resources/project/Demo");
+
+    let report = gather_report(&project_root).unwrap();
+
+    assert_eq!(report.name, "Demo");
+    assert_eq!(report.engine_association.as_deref(), Some("5.3"));
+
+    let mut targets = report
+        .targets
+        .iter()
+        .map(|target| (target.name.as_str(), target.r#type.as_deref()))
+        .collect::<Vec<_>>();
+    targets.sort();
+    assert_eq!(
+        targets,
+        vec![("Demo", Some("Game")), ("DemoEditor", Some("Editor"))]
+    );
+
+    let mut modules = report
+        .modules
+        .iter()
+        .map(|module| {
+            (
+                module.name.as_str(),
+                module.plugin.as_deref(),
+                module.host_type.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    modules.sort();
+    assert_eq!(
+        modules,
+        vec![
+            ("Demo", None, Some("Runtime")),
+            ("DemoEditor", None, Some("Editor")),
+            ("NestedCore", Some("Nested"), Some("Runtime")),
+            ("ToolsRuntime", Some("Tools"), Some("Runtime")),
+        ]
+    );
+
+    let nested = report
+        .plugins
+        .iter()
+        .find(|plugin| plugin.name == "Nested")
+        .unwrap();
+    assert_eq!(nested.parent.as_deref(), Some("Tools"));
+
+    let export_macro = &report.export_macros[0];
+    assert_eq!(export_macro.r#macro, "DEMO_API");
+    assert_eq!(
+        export_macro.files,
+        vec![PathBuf::from("Source/Demo/Public/DemoGameMode.h")]
+    );
+}
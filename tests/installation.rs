@@ -0,0 +1,152 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use renom::{
+    installation::{detect_engine_names, EngineOptions},
+    validation::validate_name_is_not_engine_name,
+    workflows::rename_module::{rename_module, Params},
+};
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Stage an engine with a runtime module, a plugin and a build product that
+/// must not be mistaken for a module.
+fn stage_engine(name: &str) -> PathBuf {
+    let staging_dir = env::current_dir()
+        .unwrap()
+        .join("tests/temp/installation")
+        .join(name);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    let engine_root = staging_dir.join("UE_5.3");
+    write(
+        &engine_root.join("Engine/Source/Runtime/Widgets/Widgets.Build.cs"),
+        "",
+    );
+    write(
+        &engine_root.join("Engine/Plugins/FX/Particles/Particles.uplugin"),
+        "{}",
+    );
+    write(
+        &engine_root.join("Engine/Plugins/FX/Particles/Intermediate/Stale.Build.cs"),
+        "",
+    );
+    engine_root
+}
+
+fn params(engine: EngineOptions) -> Params {
+    Params {
+        project_root: PathBuf::from("tests/resources/project/Demo"),
+        module: "Demo".into(),
+        new_name: "Particles".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: None,
+        clean: false,
+        vcs: Default::default(),
+        engine,
+    }
+}
+
+#[test]
+fn detect_engine_names_should_list_modules_and_plugins_of_given_engine() {
+    let engine_root = stage_engine("engine_root");
+    let options = EngineOptions {
+        engine_root: Some(engine_root),
+        ..EngineOptions::default()
+    };
+
+    let names = detect_engine_names(Path::new("tests/resources/project/Demo"), &options).unwrap();
+
+    assert_eq!(names, vec!["Particles".to_owned(), "Widgets".to_owned()]);
+}
+
+#[test]
+fn rename_module_should_reject_names_of_engine_plugins() {
+    let engine_root = stage_engine("reject_plugin_name");
+    let options = EngineOptions {
+        engine_root: Some(engine_root),
+        ..EngineOptions::default()
+    };
+
+    assert_eq!(
+        rename_module(params(options)).err(),
+        Some("new name must not conflict with the engine module or plugin 'Particles'".into())
+    );
+}
+
+#[test]
+fn rename_module_should_resolve_engine_association_through_launcher_installed_list() {
+    let engine_root = stage_engine("launcher_installed");
+    let registry = engine_root.with_file_name("LauncherInstalled.dat");
+    let launcher_installed = serde_json::json!({
+        "InstallationList": [{
+            "InstallLocation": engine_root,
+            "AppName": "UE_5.3",
+        }]
+    });
+    fs::write(&registry, launcher_installed.to_string()).unwrap();
+    let options = EngineOptions {
+        engine_registry: Some(registry),
+        ..EngineOptions::default()
+    };
+
+    assert_eq!(
+        rename_module(params(options)).err(),
+        Some("new name must not conflict with the engine module or plugin 'Particles'".into())
+    );
+}
+
+#[test]
+fn detect_engine_names_should_fall_back_to_common_engine_modules() {
+    let project_root = env::current_dir()
+        .unwrap()
+        .join("tests/temp/installation/fallback/Demo");
+    write(&project_root.join("Demo.uproject"), "{}");
+
+    let names = detect_engine_names(&project_root, &EngineOptions::default()).unwrap();
+
+    assert!(names.contains(&"Slate".to_owned()));
+    assert_eq!(
+        validate_name_is_not_engine_name("slate", &names),
+        Err("new name must not conflict with the engine module or plugin 'Slate'".into())
+    );
+}
+
+#[test]
+fn detect_engine_names_should_resolve_engine_association_through_install_ini() {
+    let engine_root = stage_engine("install_ini");
+    let registry = engine_root.with_file_name("Install.ini");
+    fs::write(
+        &registry,
+        format!("[Installations]\n5.3={}\n", engine_root.display()),
+    )
+    .unwrap();
+    let options = EngineOptions {
+        engine_registry: Some(registry),
+        ..EngineOptions::default()
+    };
+
+    let names = detect_engine_names(Path::new("tests/resources/project/Demo"), &options).unwrap();
+
+    assert!(names.contains(&"Widgets".to_owned()));
+}
+
+#[test]
+fn detect_engine_names_should_fail_for_unlisted_engine_association() {
+    let engine_root = stage_engine("unlisted");
+    let registry = engine_root.with_file_name("Install.ini");
+    fs::write(&registry, "[Installations]\n").unwrap();
+    let options = EngineOptions {
+        engine_registry: Some(registry),
+        ..EngineOptions::default()
+    };
+
+    assert!(detect_engine_names(Path::new("tests/resources/project/Demo"), &options).is_err());
+}
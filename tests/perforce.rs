@@ -0,0 +1,129 @@
+#![cfg(unix)]
+
+use std::{
+    env, fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use renom::{
+    vcs::{Vcs, VcsOptions},
+    workflows::rename_module::{rename_module, Params},
+};
+use walkdir::WalkDir;
+
+/// A fake p4 client that logs its arguments and emulates `edit` and `move`
+/// on the local file system.
+const FAKE_P4: &str = r#"#!/bin/sh
+echo "$*" >> "$(dirname "$0")/p4.log"
+command="$1"
+shift
+case "$command" in
+    edit) chmod -R u+w "${1%/...}" 2>/dev/null ;;
+    move) mv "${1%/...}" "${2%/...}" ;;
+esac
+"#;
+
+static INSTALL_FAKE_P4: Once = Once::new();
+
+/// Put the fake p4 client first on the PATH and return its log file.
+fn install_fake_p4() -> PathBuf {
+    let bin_dir = env::current_dir().unwrap().join("tests/temp/perforce/bin");
+    INSTALL_FAKE_P4.call_once(|| {
+        fs::create_dir_all(&bin_dir).unwrap();
+        let p4 = bin_dir.join("p4");
+        fs::write(&p4, FAKE_P4).unwrap();
+        fs::set_permissions(&p4, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::write(bin_dir.join("p4.log"), "").unwrap();
+        let path = env::var_os("PATH").unwrap_or_default();
+        let paths = std::iter::once(bin_dir.clone()).chain(env::split_paths(&path));
+        env::set_var("PATH", env::join_paths(paths).unwrap());
+    });
+    bin_dir.join("p4.log")
+}
+
+/// Stage the demo project with every file read-only, as in a Perforce
+/// workspace.
+fn stage(name: &str) -> PathBuf {
+    let original_project = PathBuf::from("tests/resources/project/Demo");
+    let staging_dir = env::current_dir()
+        .unwrap()
+        .join("tests/temp/perforce")
+        .join(name);
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    let project_root = staging_dir.join("Demo");
+    for entry in WalkDir::new(&original_project)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        let target = project_root.join(entry.path().strip_prefix(&original_project).unwrap());
+        if entry.path().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+        } else {
+            fs::copy(entry.path(), &target).unwrap();
+            fs::set_permissions(&target, fs::Permissions::from_mode(0o444)).unwrap();
+        }
+    }
+    project_root
+}
+
+fn params(project_root: &Path, vcs: VcsOptions) -> Params {
+    Params {
+        project_root: project_root.to_owned(),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.with_file_name("backup")),
+        clean: false,
+        vcs,
+        engine: Default::default(),
+    }
+}
+
+fn is_read_only(path: &Path) -> bool {
+    fs::metadata(path).unwrap().permissions().readonly()
+}
+
+#[test]
+fn rename_module_should_open_files_for_edit_and_move_with_p4() {
+    let log = install_fake_p4();
+    let project_root = stage("edit_and_move");
+    let vcs = VcsOptions {
+        vcs: Some(Vcs::Perforce),
+        ..VcsOptions::default()
+    };
+
+    rename_module(params(&project_root, vcs)).unwrap();
+
+    let log = fs::read_to_string(log).unwrap();
+    let root = project_root.display();
+    assert!(log.contains(&format!("edit {}/Config/DefaultEngine.ini\n", root)));
+    assert!(log.contains(&format!(
+        "move {root}/Source/Demo/... {root}/Source/Dome/...\n",
+        root = root
+    )));
+    assert!(project_root.join("Source/Dome/Dome.Build.cs").is_file());
+    assert!(!is_read_only(
+        &project_root.join("Config/DefaultEngine.ini")
+    ));
+}
+
+#[test]
+fn rename_module_should_clear_read_only_flags_when_requested() {
+    let project_root = stage("clear_read_only");
+    let vcs = VcsOptions {
+        clear_read_only: true,
+        ..VcsOptions::default()
+    };
+
+    rename_module(params(&project_root, vcs)).unwrap();
+
+    let config = project_root.join("Config/DefaultEngine.ini");
+    assert!(!is_read_only(&config));
+    assert!(fs::read_to_string(config).unwrap().contains("/Script/Dome"));
+    assert!(!is_read_only(&project_root.join("Config/DefaultGame.ini")));
+}
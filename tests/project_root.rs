@@ -0,0 +1,96 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::copy_dir;
+use renom::{
+    detection::{detect_project_descriptor, detect_project_root, project_root_of},
+    workflows::rename_module,
+};
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Stage a copy of the demo project with a second descriptor next to the
+/// original one.
+fn stage_with_two_descriptors(name: &str) -> PathBuf {
+    let project_root = manifest_dir().join("tests/temp/project_root").join(name);
+    if project_root.is_dir() {
+        fs::remove_dir_all(&project_root).unwrap();
+    }
+    copy_dir(
+        &manifest_dir().join("tests/resources/project/Demo"),
+        &project_root,
+    );
+    fs::copy(
+        project_root.join("Demo.uproject"),
+        project_root.join("Other.uproject"),
+    )
+    .unwrap();
+    project_root
+}
+
+#[test]
+fn detect_project_root_should_walk_up_to_project_descriptor() {
+    let project_root = manifest_dir().join("tests/resources/project/Demo");
+
+    assert_eq!(
+        detect_project_root(&project_root.join("Source/Demo")),
+        Some(project_root.clone())
+    );
+    assert_eq!(detect_project_root(&project_root), Some(project_root));
+    assert_eq!(detect_project_root(&manifest_dir().join("src")), None);
+}
+
+#[test]
+fn detect_project_descriptor_should_accept_descriptor_path() {
+    let project_root = manifest_dir().join("tests/resources/project/Demo");
+    let descriptor = project_root.join("Demo.uproject");
+
+    assert_eq!(
+        detect_project_descriptor(&descriptor),
+        Ok(descriptor.clone())
+    );
+    assert_eq!(project_root_of(&descriptor), project_root);
+    assert_eq!(project_root_of(&project_root), project_root);
+}
+
+#[test]
+fn detect_project_descriptor_should_fail_for_several_descriptors() {
+    let project_root = stage_with_two_descriptors("several_descriptors");
+
+    assert_eq!(
+        detect_project_descriptor(&project_root),
+        Err(
+            "project root contains several project descriptors (Demo.uproject, Other.uproject), \
+            pass the path to one of them instead"
+                .into()
+        )
+    );
+    assert_eq!(detect_project_root(&project_root), Some(project_root));
+}
+
+#[test]
+fn rename_module_should_update_only_the_given_descriptor() {
+    let project_root = stage_with_two_descriptors("given_descriptor");
+
+    rename_module::rename_module(rename_module::Params {
+        project_root: project_root.join("Demo.uproject"),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.join(".renom/backup")),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    })
+    .unwrap();
+
+    let descriptor = fs::read_to_string(project_root.join("Demo.uproject")).unwrap();
+    let other_descriptor = fs::read_to_string(project_root.join("Other.uproject")).unwrap();
+    assert!(project_root.join("Source/Dome").is_dir());
+    assert!(descriptor.contains("\"Dome\""));
+    assert!(other_descriptor.contains("\"Demo\""));
+}
@@ -0,0 +1,46 @@
+//! Renames a project that contains the current directory. The test changes
+//! the current directory of the process, so it has a test binary of its own
+//! to keep it from affecting tests that run in parallel.
+
+mod common;
+
+use std::{env, fs, path::PathBuf};
+
+use common::copy_dir;
+use renom::workflows::rename_project::{rename_project, Params};
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+// Directories in use cannot be renamed on Windows.
+#[cfg_attr(windows, ignore)]
+#[test]
+fn rename_project_should_rename_project_containing_current_dir() {
+    let staging_dir = manifest_dir().join("tests/temp/project_root_current_dir");
+    if staging_dir.is_dir() {
+        fs::remove_dir_all(&staging_dir).unwrap();
+    }
+    let project_root = staging_dir.join("Demo");
+    copy_dir(
+        &manifest_dir().join("tests/resources/project/Demo"),
+        &project_root,
+    );
+    env::set_current_dir(project_root.join("Source")).unwrap();
+
+    let result = rename_project(Params {
+        project_root: PathBuf::from(".."),
+        new_name: "Dome".into(),
+        backup_dir: Some(staging_dir.join("backup")),
+        vcs: Default::default(),
+        engine: Default::default(),
+        clean: false,
+    });
+    let current_dir = env::current_dir().unwrap();
+    env::set_current_dir(manifest_dir()).unwrap();
+
+    result.unwrap();
+    assert!(staging_dir.join("Dome/Dome.uproject").is_file());
+    assert!(!project_root.exists());
+    assert_eq!(current_dir, staging_dir.join("Dome/Source"));
+}
@@ -0,0 +1,36 @@
+use renom::presentation::prompt::{complete_path, fuzzy_matches};
+
+#[test]
+fn fuzzy_matches_should_match_characters_in_order_ignoring_case() {
+    assert!(fuzzy_matches("", "DemoEditor  [project]"));
+    assert!(fuzzy_matches("demoed", "DemoEditor  [project]"));
+    assert!(fuzzy_matches(
+        "onbd",
+        "OnlineBuild  [Plugins/Online/OnlineBuild]"
+    ));
+    assert!(fuzzy_matches("tools rt", "ToolsRuntime  [plugin Tools]"));
+}
+
+#[test]
+fn fuzzy_matches_should_reject_characters_out_of_order() {
+    assert!(!fuzzy_matches("edde", "DemoEditor  [project]"));
+    assert!(!fuzzy_matches("demox", "DemoEditor  [project]"));
+}
+
+#[test]
+fn complete_path_should_complete_folders_and_project_descriptors() {
+    let project_root = format!(
+        "{}/tests/resources/project/Demo/",
+        env!("CARGO_MANIFEST_DIR")
+    );
+
+    assert_eq!(
+        complete_path(&format!("{}so", project_root)),
+        vec![format!("{}Source/", project_root)]
+    );
+    assert_eq!(
+        complete_path(&format!("{}Demo", project_root)),
+        vec![format!("{}Demo.uproject", project_root)]
+    );
+    assert!(complete_path(&format!("{}Missing/", project_root)).is_empty());
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{package_with_names, stage_demo_project, write_bytes};
+use renom::workflows::refs::{find_references, OccurrenceKind, Params, Subject};
+
+#[test]
+fn refs_should_split_touched_and_untouched_references() {
+    let project_root = PathBuf::from("tests/resources/project/Demo");
+    let params = Params {
+        project_root: project_root.clone(),
+        subject: Subject::Module("Demo".into()),
+    };
+
+    let references = find_references(&params).unwrap();
+
+    assert!(references.touched.iter().any(|reference| {
+        reference.path == project_root.join("Source/DemoEditor/DemoEditor.Build.cs")
+            && reference.line == Some(8)
+    }));
+    assert!(references.touched.iter().any(|reference| {
+        reference.path == project_root.join("Config/DefaultEngine.ini") && reference.line == Some(3)
+    }));
+    assert!(references.untouched.iter().any(|(reference, kind)| {
+        reference.path == project_root.join("Source/Demo/Public/DemoGameMode.h")
+            && *kind == OccurrenceKind::Comment
+    }));
+    assert!(references.untouched.iter().any(|(reference, kind)| {
+        reference.path == project_root.join("Source/Demo/Private/Demo.cpp")
+            && *kind == OccurrenceKind::StringLiteral
+    }));
+    assert!(references
+        .untouched
+        .iter()
+        .all(|(reference, _)| reference.path != project_root));
+}
+
+#[test]
+fn refs_should_report_references_in_package_name_tables() {
+    let project_root = stage_demo_project("refs/package_name_tables");
+    // Packages of both odd and even length, which once decoded as text.
+    let names = [
+        vec!["None", "/Script/Demo", "/Script/Demo.DemoGameMode"],
+        vec!["None", "/Script/Demo", "/Script/Demo.DemoGameModes"],
+    ];
+    for (index, names) in names.iter().enumerate() {
+        write_bytes(
+            &project_root.join(format!("Content/A{}.uasset", index)),
+            &package_with_names(names),
+        );
+    }
+    let params = Params {
+        project_root: project_root.clone(),
+        subject: Subject::Module("Demo".into()),
+    };
+
+    let references = find_references(&params).unwrap();
+
+    for index in 0..2 {
+        let path = project_root.join(format!("Content/A{}.uasset", index));
+        let texts = references
+            .untouched
+            .iter()
+            .filter(|(reference, _)| reference.path == path)
+            .map(|(reference, kind)| {
+                assert_eq!(*kind, OccurrenceKind::Binary);
+                reference.text.as_str()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(texts[0], "/Script/Demo");
+        assert_eq!(texts.len(), 2);
+    }
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::{stage_demo_project, write};
+use renom::workflows::rename_content_folder::{rename_content_folder, Params};
+
+#[test]
+fn rename_content_folder_should_update_config_references_and_add_redirect() {
+    let project_root = stage_demo_project("rename_content_folder/update_references");
+    fs::create_dir_all(project_root.join("Content/Maps")).unwrap();
+    fs::write(project_root.join("Content/Maps/Entry.umap"), "").unwrap();
+    let config_file = project_root.join("Config/DefaultEngine.ini");
+    let config = fs::read_to_string(&config_file).unwrap().replace(
+        "GameDefaultMap=/Game/Maps/Entry.Entry",
+        "GameDefaultMap=/Game/Maps/Entry.Entry\nEditorStartupMap=/Game/Maps/Entry.Entry",
+    )
+        + "+PackageRedirects=(OldName=\"/Game/Maps/Start\",NewName=\"/Game/Maps/Entry\")\n";
+    fs::write(&config_file, config).unwrap();
+    let plugin_config_file = project_root.join("Plugins/Tools/Config/DefaultTools.ini");
+    write(
+        &plugin_config_file,
+        "[/Script/ToolsRuntime.ToolsSettings]\nMap=/Game/Maps/Entry.Entry\n",
+    );
+
+    let params = Params {
+        project_root: project_root.clone(),
+        folder: "Maps".into(),
+        new_name: "Levels".into(),
+        backup_dir: Some(project_root.join(".renom/backup")),
+        vcs: Default::default(),
+    };
+    rename_content_folder(params).unwrap();
+
+    assert!(project_root.join("Content/Levels/Entry.umap").is_file());
+    assert!(!project_root.join("Content/Maps").exists());
+    let config = fs::read_to_string(&config_file).unwrap();
+    assert!(config.contains("GameDefaultMap=/Game/Levels/Entry.Entry"));
+    assert!(config.contains("EditorStartupMap=/Game/Levels/Entry.Entry"));
+    assert!(config.contains(
+        r#"+PackageRedirects=(OldName="/Game/Maps/Start",NewName="/Game/Levels/Entry")"#
+    ));
+    let plugin_config = fs::read_to_string(&plugin_config_file).unwrap();
+    assert!(plugin_config.contains("Map=/Game/Levels/Entry.Entry"));
+    assert!(config.contains(
+        r#"+PackageRedirects=(OldName="/Game/Maps/",NewName="/Game/Levels/",MatchSubstring=true)"#
+    ));
+}
+
+#[test]
+fn rename_content_folder_should_reject_unknown_folder() {
+    let params = Params {
+        project_root: PathBuf::from("tests/resources/project/Demo"),
+        folder: "Missing".into(),
+        new_name: "Levels".into(),
+        backup_dir: None,
+        vcs: Default::default(),
+    };
+
+    assert!(rename_content_folder(params).is_err());
+}
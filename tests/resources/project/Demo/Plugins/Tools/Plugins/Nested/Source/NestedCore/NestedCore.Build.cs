@@ -0,0 +1,9 @@
+using UnrealBuildTool;
+
+public class NestedCore : ModuleRules
+{
+	public NestedCore(ReadOnlyTargetRules Target) : base(Target)
+	{
+		PublicDependencyModuleNames.AddRange(new string[] { "Core" });
+	}
+}
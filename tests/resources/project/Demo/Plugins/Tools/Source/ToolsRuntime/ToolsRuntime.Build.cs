@@ -0,0 +1,9 @@
+using UnrealBuildTool;
+
+public class ToolsRuntime : ModuleRules
+{
+	public ToolsRuntime(ReadOnlyTargetRules Target) : base(Target)
+	{
+		PublicDependencyModuleNames.AddRange(new string[] { "Core", "NestedCore" });
+	}
+}
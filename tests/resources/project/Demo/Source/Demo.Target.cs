@@ -0,0 +1,12 @@
+using UnrealBuildTool;
+using System.Collections.Generic;
+
+public class DemoTarget : TargetRules
+{
+	public DemoTarget(TargetInfo Target) : base(Target)
+	{
+		Type = TargetType.Game;
+		DefaultBuildSettings = BuildSettingsVersion.V2;
+		ExtraModuleNames.AddRange(new string[] { "Demo" });
+	}
+}
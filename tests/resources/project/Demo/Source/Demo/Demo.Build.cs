@@ -0,0 +1,13 @@
+using UnrealBuildTool;
+
+public class Demo : ModuleRules
+{
+	public Demo(ReadOnlyTargetRules Target) : base(Target)
+	{
+		PCHUsage = PCHUsageMode.UseExplicitOrSharedPCHs;
+
+		PublicDependencyModuleNames.AddRange(new string[] { "Core", "CoreUObject", "Engine" });
+
+		PrivateDependencyModuleNames.AddRange(new string[] { "ToolsRuntime" });
+	}
+}
@@ -0,0 +1,12 @@
+#pragma once
+
+#include "CoreMinimal.h"
+#include "GameFramework/GameModeBase.h"
+#include "DemoGameMode.generated.h"
+
+// Game mode for the Demo project.
+UCLASS()
+class DEMO_API ADemoGameMode : public AGameModeBase
+{
+	GENERATED_BODY()
+};
@@ -0,0 +1,12 @@
+using UnrealBuildTool;
+using System.Collections.Generic;
+
+public class DemoEditorTarget : TargetRules
+{
+	public DemoEditorTarget(TargetInfo Target) : base(Target)
+	{
+		Type = TargetType.Editor;
+		DefaultBuildSettings = BuildSettingsVersion.V2;
+		ExtraModuleNames.AddRange(new string[] { "Demo", "DemoEditor" });
+	}
+}
@@ -0,0 +1,10 @@
+using UnrealBuildTool;
+
+public class DemoEditor : ModuleRules
+{
+	public DemoEditor(ReadOnlyTargetRules Target) : base(Target)
+	{
+		PublicDependencyModuleNames.AddRange(new string[] { "Core", "CoreUObject", "Engine", "UnrealEd" });
+		PrivateDependencyModuleNames.Add("Demo");
+	}
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use renom::{
+    changes::{diff_lines, file_type, group_by_file_type, Change, RenameFile, ReplaceInFile},
+    workflows::rename_module::{plan_module_rename, Params},
+};
+
+#[test]
+fn file_type_should_prefer_compound_suffixes_over_extensions() {
+    assert_eq!(
+        file_type(Path::new("Source/Demo/Demo.Build.cs")),
+        "Build.cs"
+    );
+    assert_eq!(file_type(Path::new("Source/Demo.Target.cs")), "Target.cs");
+    assert_eq!(file_type(Path::new("Source/Demo/Demo.cpp")), "cpp");
+    assert_eq!(file_type(Path::new("Source/Demo")), "folder");
+}
+
+#[test]
+fn group_by_file_type_should_count_changes_per_file_type() {
+    let changeset = vec![
+        Change::RenameFile(RenameFile::new("Source/Demo", "Source/Dome")),
+        Change::ReplaceInFile(ReplaceInFile::new("Source/Demo/Demo.h", "DEMO", "DOME")),
+        Change::ReplaceInFile(ReplaceInFile::new("Source/Demo/Demo.cpp", "Demo", "Dome")),
+        Change::ReplaceInFile(ReplaceInFile::new("Source/Demo/Other.h", "DEMO", "DOME")),
+    ];
+
+    let counts = group_by_file_type(&changeset)
+        .into_iter()
+        .map(|(file_type, changes)| (file_type, changes.len()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        counts,
+        vec![
+            ("cpp".to_owned(), 1),
+            ("folder".to_owned(), 1),
+            ("h".to_owned(), 2)
+        ]
+    );
+}
+
+#[test]
+fn group_by_file_type_should_cover_module_rename() {
+    let changeset = plan_module_rename(&Params {
+        project_root: PathBuf::from("tests/resources/project/Demo"),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: None,
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    })
+    .unwrap();
+
+    let groups = group_by_file_type(&changeset);
+
+    let file_types = groups
+        .iter()
+        .map(|(file_type, _)| file_type.as_str())
+        .collect::<Vec<_>>();
+    assert!(file_types.contains(&"Build.cs"));
+    assert!(file_types.contains(&"uproject"));
+    let total: usize = groups.iter().map(|(_, changes)| changes.len()).sum();
+    assert_eq!(total, changeset.len());
+}
+
+#[test]
+fn diff_lines_should_list_changed_lines_with_line_numbers() {
+    let before = "#include \"Demo.h\"\n\nIMPLEMENT_MODULE(FDemo, Demo);\n";
+    let after = "#include \"Dome.h\"\n\nIMPLEMENT_MODULE(FDemo, Dome);\n";
+
+    assert_eq!(
+        diff_lines(before, after),
+        vec![
+            "-    1 #include \"Demo.h\"",
+            "+    1 #include \"Dome.h\"",
+            "-    3 IMPLEMENT_MODULE(FDemo, Demo);",
+            "+    3 IMPLEMENT_MODULE(FDemo, Dome);",
+        ]
+    );
+}
+
+#[test]
+fn diff_lines_should_list_inserted_lines() {
+    let before = "[Section]\nKey=Value\n";
+    let after = "[Section]\nKey=Value\nOther=Value\n";
+
+    assert_eq!(diff_lines(before, after), vec!["+    3 Other=Value"]);
+}
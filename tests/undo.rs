@@ -0,0 +1,74 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::stage_demo_project;
+use renom::{
+    backup::{find_backup_sets, BackupKind},
+    workflows::{
+        rename_module::{rename_module, Params},
+        undo,
+    },
+};
+
+/// Stage a copy of the demo project and rename its module, returning the
+/// project root and the backup root of the rename.
+fn stage_renamed_module(name: &str) -> (PathBuf, PathBuf) {
+    let project_root = stage_demo_project(&format!("undo/{}", name));
+    let backup_root = project_root.with_file_name("backup");
+    rename_module(Params {
+        project_root: project_root.clone(),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(backup_root.clone()),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    })
+    .unwrap();
+    (project_root, backup_root)
+}
+
+#[test]
+fn rename_should_record_renames_in_backup_set() {
+    let (_, backup_root) = stage_renamed_module("record_renames");
+
+    let manifests = find_backup_sets(&backup_root).unwrap();
+
+    assert_eq!(manifests.len(), 1);
+    assert!(manifests[0]
+        .entries
+        .iter()
+        .any(|entry| entry.kind == BackupKind::Rename && entry.renamed_to.is_some()));
+}
+
+#[test]
+fn undo_should_restore_renamed_module_and_remove_backup_set() {
+    let (project_root, backup_root) = stage_renamed_module("restore_module");
+    let original_descriptor =
+        fs::read_to_string("tests/resources/project/Demo/Demo.uproject").unwrap();
+    let id = find_backup_sets(&backup_root).unwrap()[0].id.clone();
+
+    undo(&backup_root, &id).unwrap();
+
+    assert!(project_root.join("Source/Demo").is_dir());
+    assert!(!project_root.join("Source/Dome").exists());
+    assert_eq!(
+        fs::read_to_string(project_root.join("Demo.uproject")).unwrap(),
+        original_descriptor
+    );
+    assert!(find_backup_sets(&backup_root).unwrap().is_empty());
+}
+
+#[test]
+fn undo_should_fail_for_unknown_or_invalid_backup_set() {
+    let (_, backup_root) = stage_renamed_module("unknown_set");
+
+    let id = find_backup_sets(&backup_root).unwrap()[0].id.clone();
+
+    assert!(undo(&backup_root, "missing").is_err());
+    assert!(undo(&backup_root, &format!("../backup/{}", id)).is_err());
+    assert!(backup_root.join(id).is_dir());
+}
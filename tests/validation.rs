@@ -0,0 +1,177 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::stage_demo_project;
+use renom::{
+    validation::{validate_new_name, NameKind},
+    workflows::{
+        rename_module::{rename_module, Params},
+        rename_project, rename_target,
+    },
+};
+
+#[test]
+fn validate_new_name_should_accept_unreal_identifiers() {
+    assert!(validate_new_name("Demo", "Dome", NameKind::Module, &["Demo"]).is_ok());
+    assert!(validate_new_name("Tools", "Dome_2", NameKind::Plugin, &[]).is_ok());
+    assert!(validate_new_name("Maps", "Core", NameKind::ContentFolder, &["Maps"]).is_ok());
+}
+
+#[test]
+fn validate_new_name_should_explain_each_rule() {
+    let cases = [
+        ("", NameKind::Module, "new name must not be empty"),
+        (
+            "ThisProjectNameIsFarTooLong",
+            NameKind::Project,
+            "new name must not be longer than 20 characters",
+        ),
+        (
+            "Démo",
+            NameKind::Module,
+            "new name must contain ASCII characters only",
+        ),
+        (
+            "2Demo",
+            NameKind::Module,
+            "new name must start with a letter",
+        ),
+        (
+            "_Demo",
+            NameKind::Target,
+            "new name must start with a letter",
+        ),
+        (
+            "De-mo",
+            NameKind::Plugin,
+            "new name must be comprised of alphanumeric characters and underscores only",
+        ),
+        (
+            "class",
+            NameKind::Module,
+            "new name must not be the C++ keyword 'class'",
+        ),
+        (
+            "coreuobject",
+            NameKind::Plugin,
+            "new name must not be 'CoreUObject', which is reserved by Unreal Engine",
+        ),
+    ];
+    for (name, kind, error) in cases.iter() {
+        assert_eq!(
+            validate_new_name("Demo", name, *kind, &[]),
+            Err(error.to_string()),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn validate_new_name_should_reject_collisions_ignoring_case() {
+    assert_eq!(
+        validate_new_name(
+            "Demo",
+            "DEMOEDITOR",
+            NameKind::Module,
+            &["Demo", "DemoEditor"]
+        ),
+        Err("new name must not conflict with the existing module or plugin 'DemoEditor'".into())
+    );
+}
+
+#[test]
+fn validate_new_name_should_allow_changing_only_the_case_of_the_current_name() {
+    let modules = ["Demo", "DemoEditor"];
+    assert!(validate_new_name("Demo", "DEMO", NameKind::Module, &modules).is_ok());
+    assert_eq!(
+        validate_new_name("Demo", "Demo", NameKind::Module, &modules),
+        Err("new name must be different than current name".into())
+    );
+}
+
+#[test]
+fn rename_module_should_reject_names_starting_with_digit() {
+    let params = Params {
+        project_root: PathBuf::from("tests/resources/project/Demo"),
+        module: "Demo".into(),
+        new_name: "3Demo".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: None,
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+
+    assert_eq!(
+        rename_module(params).err(),
+        Some("new name must start with a letter".into())
+    );
+}
+
+#[test]
+fn rename_module_should_reject_names_of_plugins_ignoring_case() {
+    let params = Params {
+        project_root: PathBuf::from("tests/resources/project/Demo"),
+        module: "Demo".into(),
+        new_name: "TOOLS".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: None,
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    };
+
+    assert_eq!(
+        rename_module(params).err(),
+        Some("new name must not conflict with the existing module or plugin 'Tools'".into())
+    );
+}
+
+#[test]
+fn rename_module_target_and_project_should_accept_the_same_name_in_sequence() {
+    let project_root = stage_demo_project("validation/same_name");
+    let backup_dir = project_root.with_file_name("backup");
+
+    for (module, new_name) in [("Demo", "Spyro"), ("DemoEditor", "SpyroEditor")] {
+        rename_module(Params {
+            project_root: project_root.clone(),
+            module: module.into(),
+            new_name: new_name.into(),
+            scan_assets: false,
+            rewrite_assets: false,
+            backup_dir: Some(backup_dir.clone()),
+            clean: false,
+            vcs: Default::default(),
+            engine: Default::default(),
+        })
+        .unwrap();
+        rename_target::rename_target(rename_target::Params {
+            project_root: project_root.clone(),
+            target: module.into(),
+            new_name: new_name.into(),
+            backup_dir: Some(backup_dir.clone()),
+            vcs: Default::default(),
+            engine: Default::default(),
+            clean: false,
+        })
+        .unwrap();
+    }
+    rename_project::rename_project(rename_project::Params {
+        project_root: project_root.clone(),
+        new_name: "Spyro".into(),
+        backup_dir: Some(backup_dir),
+        clean: false,
+        vcs: Default::default(),
+        engine: Default::default(),
+    })
+    .unwrap();
+
+    let project_root = project_root.with_file_name("Spyro");
+    assert!(project_root.join("Spyro.uproject").is_file());
+    assert!(project_root.join("Source/Spyro/Spyro.Build.cs").is_file());
+    assert!(project_root.join("Source/SpyroEditor.Target.cs").is_file());
+}
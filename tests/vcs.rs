@@ -0,0 +1,129 @@
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::stage_demo_project;
+use renom::{
+    vcs::{Git, Vcs, VcsOptions, VersionControl},
+    workflows::rename_module::{rename_module, Params},
+};
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Stage the demo project in a fresh git repository with a single commit.
+fn stage(name: &str) -> PathBuf {
+    let project_root = stage_demo_project(&format!("vcs/{}", name));
+    git(&project_root, &["init", "--quiet"]);
+    git(&project_root, &["config", "user.name", "Renom"]);
+    git(
+        &project_root,
+        &["config", "user.email", "renom@example.com"],
+    );
+    git(&project_root, &["add", "--all"]);
+    git(
+        &project_root,
+        &["commit", "--quiet", "--message", "Initial"],
+    );
+    project_root
+}
+
+fn params(project_root: &Path, vcs: VcsOptions) -> Params {
+    Params {
+        project_root: project_root.to_owned(),
+        module: "Demo".into(),
+        new_name: "Dome".into(),
+        scan_assets: false,
+        rewrite_assets: false,
+        backup_dir: Some(project_root.with_file_name("backup")),
+        clean: false,
+        vcs,
+        engine: Default::default(),
+    }
+}
+
+#[test]
+fn rename_module_should_stage_renames_and_commit_with_git() {
+    let project_root = stage("commit_renames");
+    let vcs = VcsOptions {
+        vcs: Some(Vcs::Git),
+        allow_dirty: false,
+        commit: true,
+        ..VcsOptions::default()
+    };
+
+    rename_module(params(&project_root, vcs)).unwrap();
+
+    assert_eq!(git(&project_root, &["status", "--porcelain"]), "");
+    let message = git(&project_root, &["log", "-1", "--format=%B"]);
+    assert!(message.starts_with("Rename module Demo to Dome\n"));
+    assert!(message.contains("Rename Source/Demo to Source/Dome"));
+    let renames = git(
+        &project_root,
+        &[
+            "show",
+            "--name-status",
+            "--format=",
+            "--find-renames",
+            "HEAD",
+        ],
+    );
+    assert!(renames
+        .lines()
+        .any(|line| line.starts_with('R') && line.ends_with("Source/Dome/Dome.Build.cs")));
+}
+
+#[test]
+fn rename_module_should_refuse_dirty_working_trees() {
+    let project_root = stage("dirty_working_tree");
+    let config = project_root.join("Config/DefaultEngine.ini");
+    fs::write(&config, "[URL]\nGameName=Dirty\n").unwrap();
+    let vcs = VcsOptions {
+        vcs: Some(Vcs::Git),
+        allow_dirty: false,
+        commit: false,
+        ..VcsOptions::default()
+    };
+
+    assert!(rename_module(params(&project_root, vcs)).is_err());
+    assert!(project_root.join("Source/Demo").is_dir());
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "[URL]\nGameName=Dirty\n"
+    );
+}
+
+#[test]
+fn git_rename_should_fall_back_to_file_system_for_unstageable_renames() {
+    let project_root = stage("unstageable_renames");
+    fs::write(project_root.join("Untracked.txt"), "").unwrap();
+
+    Git.rename(
+        &project_root.join("Untracked.txt"),
+        &project_root.join("Renamed.txt"),
+    )
+    .unwrap();
+    fs::write(project_root.join(".git/index.lock"), "").unwrap();
+    Git.rename(
+        &project_root.join("Demo.uproject"),
+        &project_root.join("Dome.uproject"),
+    )
+    .unwrap();
+    fs::remove_file(project_root.join(".git/index.lock")).unwrap();
+
+    assert!(project_root.join("Renamed.txt").is_file());
+    assert!(project_root.join("Dome.uproject").is_file());
+    let status = git(&project_root, &["status", "--porcelain"]);
+    assert!(status.lines().any(|line| line == " D Demo.uproject"));
+}